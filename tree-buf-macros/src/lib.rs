@@ -15,14 +15,14 @@ use {
 
 
 
-#[proc_macro_derive(Write)]
+#[proc_macro_derive(Write, attributes(tree_buf))]
 pub fn write_macro_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let output = impl_write_macro(&ast);
     proc_macro::TokenStream::from(output)
 }
 
-#[proc_macro_derive(Read)]
+#[proc_macro_derive(Read, attributes(tree_buf))]
 pub fn read_macro_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let output = impl_read_macro(&ast);
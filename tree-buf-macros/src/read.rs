@@ -1,8 +1,11 @@
 use {
-    crate::utils::{canonical_ident, get_named_fields, NamedField},
-    proc_macro2::TokenStream,
+    crate::utils::{
+        add_bounds, canonical_ident, get_named_fields, get_transparent_field, get_unnamed_fields, get_variant_fields, read_generics, ContainerAttributes, NamedField, UnnamedField,
+        VariantField,
+    },
+    proc_macro2::{Ident, TokenStream},
     quote::ToTokens,
-    syn::{Data, DataEnum, DataStruct, DeriveInput, Fields, FieldsUnnamed},
+    syn::{ext::IdentExt, parse_quote, Data, DataEnum, DataStruct, DeriveInput, Fields, FieldsUnnamed, Member, Type},
 };
 
 pub fn impl_read_macro(ast: &DeriveInput) -> TokenStream {
@@ -14,59 +17,75 @@ pub fn impl_read_macro(ast: &DeriveInput) -> TokenStream {
 }
 
 fn impl_struct_read(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream {
-    let fields = get_named_fields(data_struct);
-    let name = &ast.ident;
+    if ContainerAttributes::parse(&ast.attrs).transparent {
+        return impl_transparent_read(ast, data_struct);
+    }
 
-    let inits = fields
-        .iter()
-        .map(|NamedField { ident, canon_str, .. }| {
-            quote! {
-                let #ident = fields.remove(#canon_str).unwrap_or_default();
-            }
-        })
-        .collect::<Vec<_>>();
-    let unwraps = fields
-        .iter()
-        .map(|NamedField { ident, .. }| {
-            quote! {
-                #ident: #ident?,
-            }
-        })
-        .collect::<Vec<_>>();
+    match &data_struct.fields {
+        Fields::Named(_) => impl_named_struct_read(ast, data_struct),
+        Fields::Unnamed(fields_unnamed) if !fields_unnamed.unnamed.is_empty() => impl_tuple_struct_read(ast, fields_unnamed),
+        // Both `struct Unit;` and `struct Unit();` carry no data
+        _ => impl_unit_struct_read(ast),
+    }
+}
+
+/// A closure which adds the field or variant to the path of an error from reading it.
+fn error_context(member: &Member) -> TokenStream {
+    match member {
+        Member::Named(ident) => {
+            let name = ident.unraw().to_string();
+            quote! { |e: ::tree_buf::ReadError| e.in_field(#name) }
+        }
+        Member::Unnamed(index) => {
+            let index = index.index as usize;
+            quote! { |e: ::tree_buf::ReadError| e.in_index(#index) }
+        }
+    }
+}
 
-    let mut parallel_lhs = quote! {};
-    let mut reads_parallel_rhs = quote! {};
-    let mut news_parallel_rhs = quote! {};
+/// The expressions to read a field at the root and to create its ReaderArray,
+/// given a variable with the field's branch of the same name as the field.
+fn field_reads(ident: &Ident, ty: &Type, context: &TokenStream) -> (TokenStream, TokenStream) {
+    (
+        quote! {
+            <#ty as ::tree_buf::internal::Readable<'tree_buf>>::read(
+                #ident,
+                options,
+            ).map_err(#context)
+        },
+        quote! {
+            ::tree_buf::internal::ReaderArray::new(#ident, options).map_err(#context)
+        },
+    )
+}
+
+/// Builds the left and right hand sides of reading each field in parallel.
+/// Takes the field, the expression to read it at the root, and the expression to create its ReaderArray.
+/// Returns the pattern, the expression to use at the root, and the expression to use in arrays.
+fn parallel_reads<'a>(fields: impl Iterator<Item = (&'a Ident, TokenStream, TokenStream)>) -> (TokenStream, TokenStream, TokenStream) {
+    let mut parallel_lhs = quote! { () };
+    let mut reads_parallel_rhs = quote! { () };
+    let mut news_parallel_rhs = quote! { () };
     let mut is_first = true;
 
-    for NamedField { ident, ty, .. } in fields.iter() {
+    for (ident, read, new) in fields {
         if is_first {
             is_first = false;
             parallel_lhs = quote! { #ident };
-            reads_parallel_rhs = quote! {
-                <#ty as ::tree_buf::internal::Readable>::read(
-                    #ident,
-                    options,
-                )
-            };
-            news_parallel_rhs = quote! {
-                ::tree_buf::internal::ReaderArray::new(#ident, options)
-            };
+            reads_parallel_rhs = read;
+            news_parallel_rhs = new;
         } else {
             parallel_lhs = quote! { (#ident, #parallel_lhs) };
             reads_parallel_rhs = quote! {
                 ::tree_buf::internal::parallel(
-                    || <#ty as ::tree_buf::internal::Readable>::read(
-                        #ident,
-                        options,
-                    ),
+                    || #read,
                     || #reads_parallel_rhs,
                     options
                 )
             };
             news_parallel_rhs = quote! {
                 ::tree_buf::internal::parallel(
-                    || ::tree_buf::internal::ReaderArray::new(#ident, options),
+                    || #new,
                     || #news_parallel_rhs,
                     options
                 )
@@ -74,23 +93,109 @@ fn impl_struct_read(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
         }
     }
 
-    let array_fields = fields.iter().map(|NamedField { ident, ty, .. }| {
-        quote! {
-            #ident: <#ty as ::tree_buf::internal::Readable>::ReaderArray
+    (parallel_lhs, reads_parallel_rhs, news_parallel_rhs)
+}
+
+/// Removes the branch for a field from the fields of an object, trying each alias in turn.
+fn take_field(canon_str: &str, aliases: &[String]) -> TokenStream {
+    quote! {
+        fields.remove(#canon_str)#(.or_else(|| fields.remove(#aliases)))*
+    }
+}
+
+fn impl_named_struct_read(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream {
+    let (skipped, fields): (Vec<_>, Vec<_>) = get_named_fields(data_struct).into_iter().partition(|f| f.attributes.skip);
+    let name = &ast.ident;
+
+    let inits = fields
+        .iter()
+        .map(|NamedField { ident, canon_str, attributes, .. }| {
+            let take = take_field(canon_str, &attributes.aliases);
+            // Fields with a default keep the Option, so that the default is
+            // used instead of reading from void when the field is missing.
+            if attributes.default.is_some() {
+                quote! { let #ident = #take; }
+            } else {
+                quote! { let #ident = #take.unwrap_or_default(); }
+            }
+        })
+        .collect::<Vec<_>>();
+    let unwraps = fields
+        .iter()
+        .map(|NamedField { ident, .. }| {
+            quote! {
+                #ident: #ident?,
+            }
+        })
+        .collect::<Vec<_>>();
+    let skipped_values = skipped
+        .iter()
+        .map(|NamedField { ident, attributes, .. }| {
+            let default = attributes.default_value();
+            quote! {
+                #ident: #default,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let (parallel_lhs, reads_parallel_rhs, news_parallel_rhs) = parallel_reads(fields.iter().map(|NamedField { ident, ty, attributes, .. }| {
+        let (read, new) = field_reads(ident, ty, &error_context(&Member::Named((*ident).clone())));
+        if attributes.default.is_some() {
+            let default = attributes.default_value();
+            let read = quote! {
+                match #ident {
+                    Some(#ident) => #read,
+                    None => Ok(#default),
+                }
+            };
+            let new = quote! {
+                match #ident {
+                    Some(#ident) => #new.map(Some),
+                    None => Ok(None),
+                }
+            };
+            (*ident, read, new)
+        } else {
+            (*ident, read, new)
+        }
+    }));
+
+    let array_fields = fields.iter().map(|NamedField { ident, ty, attributes, .. }| {
+        if attributes.default.is_some() {
+            quote! {
+                // None when the field is missing, and so uses the default
+                #ident: Option<<#ty as ::tree_buf::internal::Readable<'tree_buf>>::ReaderArray>
+            }
+        } else {
+            quote! {
+                #ident: <#ty as ::tree_buf::internal::Readable<'tree_buf>>::ReaderArray
+            }
         }
     });
 
-    let read_nexts = fields.iter().map(|NamedField { ident, .. }| {
-        quote! {
-            // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
-            #ident: match self.#ident.read_next() { Ok(v) => v, Err(e) => { return Err(e.into()); } },
+    let read_nexts = fields.iter().map(|NamedField { ident, attributes, .. }| {
+        let context = error_context(&Member::Named((*ident).clone()));
+        if attributes.default.is_some() {
+            let default = attributes.default_value();
+            quote! {
+                // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
+                #ident: match &mut self.#ident {
+                    Some(r) => match r.read_next() { Ok(v) => v, Err(e) => { return Err((#context)(e.into())); } },
+                    None => #default,
+                },
+            }
+        } else {
+            quote! {
+                // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
+                #ident: match self.#ident.read_next() { Ok(v) => v, Err(e) => { return Err((#context)(e.into())); } },
+            }
         }
     });
 
     let read = quote! {
         let mut fields = match sticks {
             ::tree_buf::internal::DynRootBranch::Object { fields } => fields,
-            _ => return Err(::tree_buf::ReadError::SchemaMismatch),
+            other => return Err(::tree_buf::ReadError::schema_mismatch::<Self>(other.type_name())),
         };
 
         #(#inits)*
@@ -99,12 +204,13 @@ fn impl_struct_read(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
 
         Ok(Self {
             #(#unwraps)*
+            #(#skipped_values)*
         })
     };
     let new = quote! {
         let mut fields = match sticks {
             ::tree_buf::internal::DynArrayBranch::Object { fields } => fields,
-            _ => return Err(::tree_buf::ReadError::SchemaMismatch),
+            other => return Err(::tree_buf::ReadError::schema_mismatch::<Self::Read>(other.type_name())),
         };
 
         #(#inits)*
@@ -113,6 +219,102 @@ fn impl_struct_read(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
 
         Ok(Self {
             #(#unwraps)*
+            tree_buf_lifetime: ::std::marker::PhantomData,
+        })
+    };
+
+    let read_next = quote! {
+        Ok(#name {
+            #(#read_nexts)*
+            #(#skipped_values)*
+        })
+    };
+
+    fill_read_skeleton(ast, read, array_fields, new, read_next)
+}
+
+fn impl_tuple_struct_read(ast: &DeriveInput, fields_unnamed: &FieldsUnnamed) -> TokenStream {
+    let fields = get_unnamed_fields(fields_unnamed);
+    let name = &ast.ident;
+    let num_fields = fields.len();
+
+    let inits = fields
+        .iter()
+        .map(|UnnamedField { ident, .. }| {
+            quote! {
+                // This unwrap is ok because we verified the len already. See also abb368f2-6c99-4c44-8f9f-4b00868adaaf
+                let #ident = fields.next().unwrap();
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let (parallel_lhs, reads_parallel_rhs, news_parallel_rhs) = parallel_reads(fields.iter().map(|UnnamedField { ident, index, ty }| {
+        let (read, new) = field_reads(ident, ty, &error_context(&Member::Unnamed(index.clone())));
+        (ident, read, new)
+    }));
+
+    let root_unwraps = fields.iter().map(|UnnamedField { ident, index, .. }| {
+        quote! {
+            #index: #ident?,
+        }
+    });
+    let array_unwraps = fields.iter().map(|UnnamedField { ident, .. }| {
+        quote! {
+            #ident: #ident?,
+        }
+    });
+
+    let array_fields = fields.iter().map(|UnnamedField { ident, ty, .. }| {
+        quote! {
+            #ident: <#ty as ::tree_buf::internal::Readable<'tree_buf>>::ReaderArray
+        }
+    });
+
+    let read_nexts = fields.iter().map(|UnnamedField { ident, index, .. }| {
+        let context = error_context(&Member::Unnamed(index.clone()));
+        quote! {
+            // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
+            #index: match self.#ident.read_next() { Ok(v) => v, Err(e) => { return Err((#context)(e.into())); } },
+        }
+    });
+
+    let read = quote! {
+        let fields = match sticks {
+            ::tree_buf::internal::DynRootBranch::Tuple { fields } => fields,
+            other => return Err(::tree_buf::ReadError::schema_mismatch::<Self>(other.type_name())),
+        };
+        // See also abb368f2-6c99-4c44-8f9f-4b00868adaaf
+        if fields.len() != #num_fields {
+            return Err(::tree_buf::ReadError::schema_mismatch::<Self>("Tuple"));
+        }
+        let mut fields = fields.into_iter();
+
+        #(#inits)*
+
+        let #parallel_lhs = #reads_parallel_rhs;
+
+        Ok(Self {
+            #(#root_unwraps)*
+        })
+    };
+    let new = quote! {
+        let fields = match sticks {
+            ::tree_buf::internal::DynArrayBranch::Tuple { fields } => fields,
+            other => return Err(::tree_buf::ReadError::schema_mismatch::<Self::Read>(other.type_name())),
+        };
+        // See also abb368f2-6c99-4c44-8f9f-4b00868adaaf
+        if fields.len() != #num_fields {
+            return Err(::tree_buf::ReadError::schema_mismatch::<Self::Read>("Tuple"));
+        }
+        let mut fields = fields.into_iter();
+
+        #(#inits)*
+
+        let #parallel_lhs = #news_parallel_rhs;
+
+        Ok(Self {
+            #(#array_unwraps)*
+            tree_buf_lifetime: ::std::marker::PhantomData,
         })
     };
 
@@ -125,16 +327,81 @@ fn impl_struct_read(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
     fill_read_skeleton(ast, read, array_fields, new, read_next)
 }
 
+fn impl_unit_struct_read(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let array_fields = std::iter::empty::<TokenStream>();
+
+    let read = quote! {
+        let _ = options;
+        match sticks {
+            ::tree_buf::internal::DynRootBranch::Void => Ok(Self {}),
+            other => Err(::tree_buf::ReadError::schema_mismatch::<Self>(other.type_name())),
+        }
+    };
+    let new = quote! {
+        let _ = options;
+        match sticks {
+            ::tree_buf::internal::DynArrayBranch::Void => Ok(Self {
+                tree_buf_lifetime: ::std::marker::PhantomData,
+            }),
+            other => Err(::tree_buf::ReadError::schema_mismatch::<Self::Read>(other.type_name())),
+        }
+    };
+
+    let read_next = quote! {
+        Ok(#name {})
+    };
+
+    fill_read_skeleton(ast, read, array_fields, new, read_next)
+}
+
+/// Reads the only field in place of the struct, without any wrapping.
+fn impl_transparent_read(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream {
+    let (member, ty) = get_transparent_field(data_struct);
+    let name = &ast.ident;
+
+    let array_fields = std::iter::once(quote! {
+        tree_buf_inner: <#ty as ::tree_buf::internal::Readable<'tree_buf>>::ReaderArray
+    });
+
+    let read = quote! {
+        Ok(Self {
+            #member: <#ty as ::tree_buf::internal::Readable<'tree_buf>>::read(sticks, options)?,
+        })
+    };
+    let new = quote! {
+        Ok(Self {
+            tree_buf_inner: ::tree_buf::internal::ReaderArray::new(sticks, options)?,
+            tree_buf_lifetime: ::std::marker::PhantomData,
+        })
+    };
+
+    let read_next = quote! {
+        // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
+        Ok(#name {
+            #member: match self.tree_buf_inner.read_next() { Ok(v) => v, Err(e) => { return Err(e.into()); } },
+        })
+    };
+
+    fill_read_skeleton(ast, read, array_fields, new, read_next)
+}
+
 fn fill_read_skeleton<A: ToTokens>(ast: &DeriveInput, read: impl ToTokens, array_fields: impl Iterator<Item = A>, new: impl ToTokens, read_next: impl ToTokens) -> TokenStream {
     let name = &ast.ident;
     let vis = &ast.vis;
     let array_reader_name = format_ident!("{}TreeBufReaderArray", name);
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let generics = add_bounds(
+        &read_generics(&ast.generics),
+        &[parse_quote!(::tree_buf::internal::Readable<'tree_buf>), parse_quote!(::std::marker::Send)],
+    );
+    let (impl_generics, reader_ty_generics, where_clause) = generics.split_for_impl();
 
     quote! {
         #[allow(non_snake_case)]
-        impl ::tree_buf::internal::Readable for #name {
-            type ReaderArray = #array_reader_name;
-            fn read(sticks: ::tree_buf::internal::DynRootBranch<'_>, options: &impl ::tree_buf::options::DecodeOptions) -> Result<Self, ::tree_buf::ReadError> {
+        impl #impl_generics ::tree_buf::internal::Readable<'tree_buf> for #name #ty_generics #where_clause {
+            type ReaderArray = #array_reader_name #reader_ty_generics;
+            fn read(sticks: ::tree_buf::internal::DynRootBranch<'tree_buf>, options: &impl ::tree_buf::options::DecodeOptions) -> Result<Self, ::tree_buf::ReadError> {
                 // TODO: Re-enable profile here
                 // See also dcebaa54-d21e-4e79-abfe-4a89cc829180
                 //::tree_buf::internal::profile!("Readable::read");
@@ -143,16 +410,18 @@ fn fill_read_skeleton<A: ToTokens>(ast: &DeriveInput, read: impl ToTokens, array
         }
 
         #[allow(non_snake_case)]
-        #vis struct #array_reader_name {
+        #vis struct #array_reader_name #impl_generics #where_clause {
             #(#array_fields,)*
+            // Reader arrays for types without fields would not otherwise use the lifetime
+            tree_buf_lifetime: ::std::marker::PhantomData<&'tree_buf ()>,
         }
 
         #[allow(non_snake_case)]
-        impl ::tree_buf::internal::ReaderArray for #array_reader_name {
-            type Read=#name;
+        impl #impl_generics ::tree_buf::internal::ReaderArray<'tree_buf> for #array_reader_name #reader_ty_generics #where_clause {
+            type Read=#name #ty_generics;
             // TODO: See if sometimes we can use Infallible here.
             type Error=::tree_buf::ReadError;
-            fn new(sticks: ::tree_buf::internal::DynArrayBranch<'_>, options: &impl ::tree_buf::options::DecodeOptions) -> Result<Self, ::tree_buf::ReadError> {
+            fn new(sticks: ::tree_buf::internal::DynArrayBranch<'tree_buf>, options: &impl ::tree_buf::options::DecodeOptions) -> Result<Self, ::tree_buf::ReadError> {
                 // TODO: Re-enable profile here
                 // See also dcebaa54-d21e-4e79-abfe-4a89cc829180
                 //::tree_buf::internal::profile!("ReaderArray::new");
@@ -169,7 +438,7 @@ fn impl_enum_read(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
     let ident = &ast.ident;
     let mut array_fields = Vec::new();
     array_fields.push(quote! {
-        tree_buf_discriminant: <u64 as ::tree_buf::Readable>::ReaderArray
+        tree_buf_discriminant: <u64 as ::tree_buf::Readable<'tree_buf>>::ReaderArray
     });
 
     let mut new_matches = Vec::new();
@@ -184,12 +453,54 @@ fn impl_enum_read(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
     for variant in data_enum.variants.iter() {
         let variant_ident = &variant.ident;
         let discriminant = canonical_ident(variant_ident);
+        let variant_context = error_context(&Member::Named(variant_ident.clone()));
 
         match &variant.fields {
-            Fields::Unit => {
+            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) if unnamed.len() == 1 => {
+                root_matches.push(quote! {
+                    #discriminant => {
+                        Self::#variant_ident(::tree_buf::internal::Readable::read(*value, options).map_err(#variant_context)?)
+                    },
+                });
+                let ty = &unnamed[0].ty;
+                array_fields.push(quote! {
+                    #variant_ident: Option<(u64, <#ty as ::tree_buf::internal::Readable<'tree_buf>>::ReaderArray)>
+                });
+                new_unpacks.push(quote! { #variant_ident: #variant_ident.transpose()?, });
+                new_parallel_lhs = quote! { (#variant_ident, #new_parallel_lhs) };
+                new_parallel_rhs = quote! {
+                    ::tree_buf::internal::parallel(
+                        || #variant_ident.map(|(i, d)| { ::tree_buf::internal::ReaderArray::new(d, options).map(|v| (i, v)).map_err(#variant_context) }),
+                        || #new_parallel_rhs,
+                        options
+                    )
+                };
+                new_matches.push(quote! {
+                    #discriminant => {
+                        if #variant_ident.is_some() {
+                            return Err(::tree_buf::ReadError::InvalidFormat);
+                        }
+                        #variant_ident = Some(
+                            (index as u64, data)
+                        );
+                    }
+                });
+                new_inits.push(quote! {
+                    let mut #variant_ident = None;
+                });
+                read_nexts.push(quote! {
+                    if let Some((d, r)) = &mut self.#variant_ident {
+                        if *d == discriminant {
+                            // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
+                            return Ok(#ident::#variant_ident(match r.read_next() { Ok(v) => v, Err(e) => return Err((#variant_context)(e.into())) }));
+                        }
+                    }
+                })
+            }
+            fields if fields.iter().next().is_none() => {
                 root_matches.push(quote! {
                     // TODO: Verify that the branch is the void type?
-                    #discriminant => Self::#variant_ident,
+                    #discriminant => Self::#variant_ident {},
                 });
                 array_fields.push(quote! {
                     #variant_ident: Option<u64>
@@ -210,76 +521,146 @@ fn impl_enum_read(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
                 read_nexts.push(quote! {
                     if let Some(d) = &mut self.#variant_ident {
                         if *d == discriminant {
-                            return Ok(#ident::#variant_ident);
+                            return Ok(#ident::#variant_ident {});
                         }
                     }
                 });
             }
-            Fields::Named(_named_fields) => todo!("Enums with named fields not yet supported by tree-buf read"),
-            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-                match unnamed.len() {
-                    // TODO: Check if this is really unreachable. It might be `Variant {}`
-                    0 => unreachable!(),
-                    1 => {
-                        root_matches.push(quote! {
-                            #discriminant => {
-                                Self::#variant_ident(::tree_buf::internal::Readable::read(*value, options)?)
-                            },
-                        });
-                        let ty = &unnamed[0].ty;
-                        array_fields.push(quote! {
-                            #variant_ident: Option<(u64, <#ty as ::tree_buf::internal::Readable>::ReaderArray)>
-                        });
-                        new_unpacks.push(quote! { #variant_ident: #variant_ident.transpose()?, });
-                        new_parallel_lhs = quote! { (#variant_ident, #new_parallel_lhs) };
-                        new_parallel_rhs = quote! {
-                            ::tree_buf::internal::parallel(
-                                || #variant_ident.map(|(i, d)| { ::tree_buf::internal::ReaderArray::new(d, options).map(|v| (i, v)) }),
-                                || #new_parallel_rhs,
-                                options
-                            )
-                        };
-                        new_matches.push(quote! {
-                            #discriminant => {
-                                if #variant_ident.is_some() {
-                                    return Err(::tree_buf::ReadError::InvalidFormat);
-                                }
-                                #variant_ident = Some(
-                                    (index as u64, data)
-                                );
-                            }
-                        });
-                        new_inits.push(quote! {
-                            let mut #variant_ident = None;
-                        });
-                        read_nexts.push(quote! {
-                            if let Some((d, r)) = &mut self.#variant_ident {
-                                if *d == discriminant {
-                                    // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
-                                    return Ok(#ident::#variant_ident(match r.read_next() { Ok(v) => v, Err(e) => return Err(e.into()) }));
-                                }
+            // Named fields and multiple unnamed fields are read from an object or tuple
+            fields => {
+                let fields = get_variant_fields(fields);
+                let num_fields = fields.len();
+                let is_named = fields[0].canon_str.is_some();
+                let variant_str = variant_ident.unraw().to_string();
+
+                // Unpacks the fields from the branch, and the expression to take each field.
+                let unpack = |branch: TokenStream, sticks: TokenStream, expected: TokenStream| {
+                    if is_named {
+                        quote! {
+                            let mut fields = match #sticks {
+                                #branch::Object { fields } => fields,
+                                other => return Err(::tree_buf::ReadError::schema_mismatch::<#expected>(other.type_name()).in_field(#variant_str)),
+                            };
+                        }
+                    } else {
+                        quote! {
+                            let fields = match #sticks {
+                                #branch::Tuple { fields } => fields,
+                                other => return Err(::tree_buf::ReadError::schema_mismatch::<#expected>(other.type_name()).in_field(#variant_str)),
+                            };
+                            // See also abb368f2-6c99-4c44-8f9f-4b00868adaaf
+                            if fields.len() != #num_fields {
+                                return Err(::tree_buf::ReadError::schema_mismatch::<#expected>("Tuple").in_field(#variant_str));
                             }
-                        })
+                            let mut fields = fields.into_iter();
+                        }
                     }
-                    _ => todo!("Enums with multiple unnamed fields not yet supported by tree-buf Read"),
-                }
+                };
+                let takes = fields
+                    .iter()
+                    .map(|VariantField { canon_str, aliases, .. }| match canon_str {
+                        Some(canon_str) => {
+                            let take = take_field(canon_str, aliases);
+                            quote! { #take.unwrap_or_default() }
+                        }
+                        // This unwrap is ok because we verified the len already. See also abb368f2-6c99-4c44-8f9f-4b00868adaaf
+                        None => quote! { fields.next().unwrap() },
+                    })
+                    .collect::<Vec<_>>();
+
+                let root_unpack = unpack(quote! { ::tree_buf::internal::DynRootBranch }, quote! { *value }, quote! { Self });
+                let root_reads = fields.iter().zip(takes.iter()).map(|(VariantField { member, ty, .. }, take)| {
+                    let context = error_context(member);
+                    quote! {
+                        #member: <#ty as ::tree_buf::internal::Readable<'tree_buf>>::read(#take, options).map_err(#context).map_err(#variant_context)?,
+                    }
+                });
+                root_matches.push(quote! {
+                    #discriminant => {
+                        #root_unpack
+                        Self::#variant_ident {
+                            #(#root_reads)*
+                        }
+                    },
+                });
+
+                let reader_arrays = fields
+                    .iter()
+                    .map(|VariantField { ty, .. }| quote! { <#ty as ::tree_buf::internal::Readable<'tree_buf>>::ReaderArray })
+                    .collect::<Vec<_>>();
+                array_fields.push(quote! {
+                    #variant_ident: Option<(u64, (#(#reader_arrays,)*))>
+                });
+                new_unpacks.push(quote! { #variant_ident: #variant_ident.transpose()?, });
+
+                let array_unpack = unpack(quote! { ::tree_buf::internal::DynArrayBranch }, quote! { d }, quote! { Self::Read });
+                let news = reader_arrays
+                    .iter()
+                    .zip(takes.iter())
+                    .zip(fields.iter())
+                    .map(|((reader_array, take), VariantField { member, .. })| {
+                        let context = error_context(member);
+                        quote! {
+                            <#reader_array as ::tree_buf::internal::ReaderArray<'tree_buf>>::new(#take, options).map_err(#context).map_err(#variant_context)?,
+                        }
+                    });
+                new_parallel_lhs = quote! { (#variant_ident, #new_parallel_lhs) };
+                new_parallel_rhs = quote! {
+                    ::tree_buf::internal::parallel(
+                        || #variant_ident.map(|(i, d)| -> Result<_, ::tree_buf::ReadError> {
+                            #array_unpack
+                            Ok((i, (#(#news)*)))
+                        }),
+                        || #new_parallel_rhs,
+                        options
+                    )
+                };
+                new_matches.push(quote! {
+                    #discriminant => {
+                        if #variant_ident.is_some() {
+                            return Err(::tree_buf::ReadError::InvalidFormat);
+                        }
+                        #variant_ident = Some(
+                            (index as u64, data)
+                        );
+                    }
+                });
+                new_inits.push(quote! {
+                    let mut #variant_ident = None;
+                });
+
+                let field_read_nexts = fields.iter().map(|VariantField { member, index, .. }| {
+                    let context = error_context(member);
+                    quote! {
+                        // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
+                        #member: match r.#index.read_next() { Ok(v) => v, Err(e) => return Err((#variant_context)((#context)(e.into()))) },
+                    }
+                });
+                read_nexts.push(quote! {
+                    if let Some((d, r)) = &mut self.#variant_ident {
+                        if *d == discriminant {
+                            return Ok(#ident::#variant_ident {
+                                #(#field_read_nexts)*
+                            });
+                        }
+                    }
+                })
             }
         }
     }
 
     let read = quote! {
         // If this is an enum,
-        if let ::tree_buf::internal::DynRootBranch::Enum { discriminant, value } = sticks {
-            Ok(
+        match sticks {
+            ::tree_buf::internal::DynRootBranch::Enum { discriminant, value } => Ok(
                 // See if it's a variant we are aware of, and that the value
                 // matches the expected data.
                 match discriminant {
                     #(#root_matches)*
-                    _ => { return Err(::tree_buf::ReadError::SchemaMismatch); },
+                    _ => { return Err(::tree_buf::ReadError::schema_mismatch::<Self>("Enum")); },
                 }
-            )
-        } else {
-            Err(::tree_buf::ReadError::SchemaMismatch)
+            ),
+            other => Err(::tree_buf::ReadError::schema_mismatch::<Self>(other.type_name())),
         }
     };
 
@@ -294,7 +675,7 @@ fn impl_enum_read(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
                     let ::tree_buf::internal::ArrayEnumVariant { ident, data } = variant;
                     match ident {
                         #(#new_matches),*
-                        _ => { return Err(::tree_buf::ReadError::SchemaMismatch); }
+                        _ => { return Err(::tree_buf::ReadError::schema_mismatch::<Self::Read>("Enum")); }
                     }
                 }
 
@@ -303,15 +684,15 @@ fn impl_enum_read(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
                 let result = Self {
                     tree_buf_discriminant: tree_buf_discriminant?,
                     #(#new_unpacks)*
+                    tree_buf_lifetime: ::std::marker::PhantomData,
                 };
 
-                // FIXME: Need to verify that the range of tree_buf_discriminant does
-                // not go beyond the number of variants listed (this would indicate a corrupt file)
-                // See also: fb0a3c86-23be-4d4a-9dbf-9c83ae6e2f0f
+                // A discriminant beyond the variants listed indicates a corrupt file.
+                // It is an error in read_next rather than checked here.
                 Ok(result)
             }
-            _ => {
-                Err(::tree_buf::ReadError::SchemaMismatch)
+            other => {
+                Err(::tree_buf::ReadError::schema_mismatch::<Self::Read>(other.type_name()))
             }
         }
     };
@@ -320,8 +701,8 @@ fn impl_enum_read(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
         let discriminant = ::tree_buf::internal::InfallibleReaderArray::read_next_infallible(&mut self.tree_buf_discriminant);
         #(#read_nexts)*
 
-        // See also: fb0a3c86-23be-4d4a-9dbf-9c83ae6e2f0f
-        todo!("Make this unreachable by verifying range");
+        // The discriminant did not match any variant in the file.
+        Err(::tree_buf::ReadError::InvalidFormat)
     };
 
     fill_read_skeleton(ast, read, array_fields.iter(), new, read_next)
@@ -1,7 +1,10 @@
 use {
     inflector::cases::camelcase::to_camel_case,
-    proc_macro2::Ident,
-    syn::{DataStruct, Fields, Type},
+    proc_macro2::{Ident, Span, TokenStream},
+    quote::ToTokens,
+    syn::{
+        Attribute, DataStruct, ExprPath, Field, Fields, FieldsUnnamed, GenericParam, Generics, Index, Lifetime, LifetimeDef, Lit, Member, Meta, NestedMeta, Type, TypeParamBound,
+    },
 };
 
 // TODO: If we limit ourselves to ASCII characters, then it's possible to just do the same as prefix-varint and have a tag bit to save binary size
@@ -21,10 +24,11 @@ pub struct NamedField<'a> {
     pub ident: &'a Ident,
     pub ty: &'a Type,
     pub canon_str: String,
+    pub attributes: FieldAttributes,
 }
 pub type NamedFields<'a> = Vec<NamedField<'a>>;
 
-pub fn get_named_fields(data_struct: &DataStruct) -> NamedFields {
+pub fn get_named_fields(data_struct: &DataStruct) -> NamedFields<'_> {
     // TODO: Lift restriction
     let fields_named = match &data_struct.fields {
         Fields::Named(fields_named) => fields_named,
@@ -36,11 +40,227 @@ pub fn get_named_fields(data_struct: &DataStruct) -> NamedFields {
         .iter()
         .map(|field| {
             let ident = field.ident.as_ref().unwrap();
+            let attributes = FieldAttributes::parse(&field.attrs);
             NamedField {
-                ident: field.ident.as_ref().unwrap(),
+                ident,
                 ty: &field.ty,
-                canon_str: canonical_ident(&ident),
+                canon_str: field_name(ident, &attributes),
+                attributes,
             }
         })
         .collect()
 }
+
+pub struct UnnamedField<'a> {
+    pub ident: Ident,
+    pub index: Index,
+    pub ty: &'a Type,
+}
+
+pub fn get_unnamed_fields(fields_unnamed: &FieldsUnnamed) -> Vec<UnnamedField<'_>> {
+    fields_unnamed
+        .unnamed
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            forbid_field_attributes(field, "Field attributes are only supported on named fields");
+            UnnamedField {
+                ident: format_ident!("_{}", i),
+                index: Index::from(i),
+                ty: &field.ty,
+            }
+        })
+        .collect()
+}
+
+/// A field of an enum variant. The binding is used in place of the field's name
+/// in patterns so that it cannot conflict with names in the generated code.
+pub struct VariantField<'a> {
+    pub member: Member,
+    pub binding: Ident,
+    pub index: Index,
+    pub ty: &'a Type,
+    /// The name written for the field, if the variant has named fields
+    pub canon_str: Option<String>,
+    /// Other names the field may be read from
+    pub aliases: Vec<String>,
+}
+
+pub fn get_variant_fields(fields: &Fields) -> Vec<VariantField<'_>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let (member, canon_str, aliases) = match &field.ident {
+                Some(ident) => {
+                    let attributes = FieldAttributes::parse(&field.attrs);
+                    if attributes.skip || attributes.default.is_some() {
+                        panic!("The skip and default attributes are not supported on fields of enum variants");
+                    }
+                    (Member::Named(ident.clone()), Some(field_name(ident, &attributes)), attributes.aliases)
+                }
+                None => {
+                    forbid_field_attributes(field, "Field attributes are only supported on named fields");
+                    (Member::Unnamed(Index::from(i)), None, Vec::new())
+                }
+            };
+            VariantField {
+                member,
+                binding: format_ident!("_{}", i),
+                index: Index::from(i),
+                ty: &field.ty,
+                canon_str,
+                aliases,
+            }
+        })
+        .collect()
+}
+
+/// The only field of a struct marked `#[tree_buf(transparent)]`
+pub fn get_transparent_field(data_struct: &DataStruct) -> (Member, &Type) {
+    let mut fields = data_struct.fields.iter();
+    match (fields.next(), fields.next()) {
+        (Some(field), None) => {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index::from(0)),
+            };
+            (member, &field.ty)
+        }
+        _ => panic!("A transparent struct must have exactly one field"),
+    }
+}
+
+/// The type id for an object or tuple with the given number of fields, along with
+/// the code to write the prefix for that type id if there are too many fields to fit.
+/// See also: fadaec14-35ad-4dc1-b6dc-6106ab811669
+pub fn fields_type_id(kind: &str, num_fields: usize) -> (TokenStream, Ident) {
+    match num_fields {
+        0..=8 => (quote! {}, format_ident!("{}{}", kind, num_fields)),
+        _ => (
+            quote! {
+                ::tree_buf::internal::encodings::varint::encode_prefix_varint(#num_fields as u64 - 9, stream.bytes);
+            },
+            format_ident!("{}N", kind),
+        ),
+    }
+}
+
+/// Options from `#[tree_buf(...)]` attributes on the type being derived
+#[derive(Default)]
+pub struct ContainerAttributes {
+    /// Write and read a struct with one field as if it were that field
+    pub transparent: bool,
+}
+
+impl ContainerAttributes {
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut result = Self::default();
+        for meta in tree_buf_metas(attrs) {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("transparent") => result.transparent = true,
+                _ => panic!("Unrecognized tree_buf attribute"),
+            }
+        }
+        result
+    }
+}
+
+/// Options from `#[tree_buf(...)]` attributes on a named field
+#[derive(Default)]
+pub struct FieldAttributes {
+    /// The name to write and read instead of the canonical form of the field's identifier
+    pub rename: Option<String>,
+    /// Other names the field may be read from, eg: for files written before a rename
+    pub aliases: Vec<String>,
+    /// Neither write nor read the field, using the default in its place
+    pub skip: bool,
+    /// The function to call for the value of the field when it is skipped or missing from the file.
+    /// When not specified, a missing field is read as if it were void. Eg: None for Option
+    pub default: Option<TokenStream>,
+}
+
+impl FieldAttributes {
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut result = Self::default();
+        for meta in tree_buf_metas(attrs) {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => result.skip = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default") => result.default = Some(quote! { ::std::default::Default::default }),
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    let value = match &name_value.lit {
+                        Lit::Str(value) => value,
+                        _ => panic!("Expected a string for the tree_buf attribute. Eg: #[tree_buf(rename = \"name\")]"),
+                    };
+                    if name_value.path.is_ident("rename") {
+                        result.rename = Some(value.value());
+                    } else if name_value.path.is_ident("alias") {
+                        result.aliases.push(value.value());
+                    } else if name_value.path.is_ident("default") {
+                        let path: ExprPath = value.parse().expect("Expected the path of a function for #[tree_buf(default = \"...\")]");
+                        result.default = Some(path.into_token_stream());
+                    } else {
+                        panic!("Unrecognized tree_buf attribute");
+                    }
+                }
+                _ => panic!("Unrecognized tree_buf attribute"),
+            }
+        }
+        result
+    }
+
+    /// The expression for the value of the field when it is not read
+    pub fn default_value(&self) -> TokenStream {
+        match &self.default {
+            Some(default) => quote! { #default() },
+            None => quote! { ::std::default::Default::default() },
+        }
+    }
+}
+
+fn field_name(ident: &Ident, attributes: &FieldAttributes) -> String {
+    match &attributes.rename {
+        Some(rename) => rename.clone(),
+        None => canonical_ident(ident),
+    }
+}
+
+fn forbid_field_attributes(field: &Field, message: &str) {
+    if !tree_buf_metas(&field.attrs).is_empty() {
+        panic!("{}", message);
+    }
+}
+
+fn tree_buf_metas(attrs: &[Attribute]) -> Vec<NestedMeta> {
+    let mut result = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("tree_buf")) {
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => result.extend(list.nested),
+            _ => panic!("Expected tree_buf attribute of the form #[tree_buf(...)]"),
+        }
+    }
+    result
+}
+
+/// Adds the bounds to each type parameter, since fields using the type parameter
+/// will need them to be written or read.
+pub fn add_bounds(generics: &Generics, bounds: &[TypeParamBound]) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.extend(bounds.iter().cloned());
+    }
+    generics
+}
+
+/// Adds the `'tree_buf` lifetime of the bytes being read to the generics of a type.
+/// It must outlive all the lifetimes of the type so that fields may borrow from the bytes.
+pub fn read_generics(generics: &Generics) -> Generics {
+    let mut lifetime = LifetimeDef::new(Lifetime::new("'tree_buf", Span::call_site()));
+    lifetime.bounds = generics.lifetimes().map(|l| l.lifetime.clone()).collect();
+    if !lifetime.bounds.is_empty() {
+        lifetime.colon_token = Some(Default::default());
+    }
+    let mut generics = generics.clone();
+    generics.params.insert(0, GenericParam::Lifetime(lifetime));
+    generics
+}
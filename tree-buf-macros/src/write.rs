@@ -1,8 +1,11 @@
 use {
-    crate::utils::{canonical_ident, get_named_fields, NamedField},
-    proc_macro2::{Ident, Span, TokenStream},
+    crate::utils::{
+        add_bounds, canonical_ident, fields_type_id, get_named_fields, get_transparent_field, get_unnamed_fields, get_variant_fields, ContainerAttributes, NamedField,
+        UnnamedField, VariantField,
+    },
+    proc_macro2::{Ident, TokenStream},
     quote::ToTokens,
-    syn::{Data, DataEnum, DataStruct, DeriveInput, Fields, FieldsUnnamed},
+    syn::{parse_quote, Data, DataEnum, DataStruct, DeriveInput, Fields, FieldsUnnamed},
 };
 
 pub fn impl_write_macro(ast: &DeriveInput) -> TokenStream {
@@ -14,7 +17,21 @@ pub fn impl_write_macro(ast: &DeriveInput) -> TokenStream {
 }
 
 fn impl_struct_write(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream {
-    let fields = get_named_fields(data_struct);
+    if ContainerAttributes::parse(&ast.attrs).transparent {
+        return impl_transparent_write(ast, data_struct);
+    }
+
+    match &data_struct.fields {
+        Fields::Named(_) => impl_named_struct_write(ast, data_struct),
+        Fields::Unnamed(fields_unnamed) if !fields_unnamed.unnamed.is_empty() => impl_tuple_struct_write(ast, fields_unnamed),
+        // Both `struct Unit;` and `struct Unit();` carry no data
+        _ => impl_unit_struct_write(ast),
+    }
+}
+
+fn impl_named_struct_write(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream {
+    let mut fields = get_named_fields(data_struct);
+    fields.retain(|f| !f.attributes.skip);
 
     let writers = fields.iter().map(|NamedField { ident, canon_str, .. }| {
         quote! {
@@ -24,9 +41,8 @@ fn impl_struct_write(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
     });
 
     let array_fields = fields.iter().map(|NamedField { ident, ty, .. }| {
-        quote! {
-            #ident: <#ty as ::tree_buf::internal::Writable>::WriterArray
-        }
+        let ident = (*ident).clone();
+        (ident, quote! { <#ty as ::tree_buf::internal::Writable>::WriterArray })
     });
 
     let buffers = fields.iter().map(|NamedField { ident, .. }| {
@@ -35,7 +51,7 @@ fn impl_struct_write(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
         }
     });
 
-    let flushers = fields.iter().map(|NamedField { ident, canon_str, ty }| {
+    let flushers = fields.iter().map(|NamedField { ident, canon_str, ty, .. }| {
         quote! {
             ::tree_buf::internal::write_ident(#canon_str, stream);
             let o = self.#ident;
@@ -43,8 +59,6 @@ fn impl_struct_write(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
         }
     });
 
-    let num_fields = fields.len();
-
     /*
     quote! {
         ::tree_buf::internal::write_fields(#num_fields, stream, |stream| move {
@@ -52,17 +66,56 @@ fn impl_struct_write(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
         })
     };
     */
-    // See also: fadaec14-35ad-4dc1-b6dc-6106ab811669
-    let (prefix, suffix) = match num_fields {
-        0..=8 => (quote! {}, Ident::new(format!("Obj{}", num_fields).as_str(), Span::call_site())),
-        _ => (
-            quote! {
-                ::tree_buf::internal::encodings::varint::encode_prefix_varint(#num_fields as u64 - 9, stream.bytes);
-            },
-            Ident::new("ObjN", Span::call_site()),
-        ),
+    let (prefix, suffix) = fields_type_id("Obj", fields.len());
+
+    let flush = quote! {
+        #prefix
+        #(#flushers)*
+        ::tree_buf::internal::ArrayTypeId::#suffix
+    };
+
+    let buffer = quote! {
+        #(#buffers)*
+    };
+
+    let write_root = quote! {
+        #prefix
+        #(#writers)*
+        ::tree_buf::internal::RootTypeId::#suffix
     };
 
+    fill_write_skeleton(ast, array_fields, buffer, flush, write_root)
+}
+
+/// Tuple structs are written the same as tuples, so that they may share a schema.
+fn impl_tuple_struct_write(ast: &DeriveInput, fields_unnamed: &FieldsUnnamed) -> TokenStream {
+    let fields = get_unnamed_fields(fields_unnamed);
+
+    let writers = fields.iter().map(|UnnamedField { index, .. }| {
+        quote! {
+            stream.write_with_id(|stream| self.#index.write_root(stream));
+        }
+    });
+
+    let array_fields = fields
+        .iter()
+        .map(|UnnamedField { ident, ty, .. }| (ident.clone(), quote! { <#ty as ::tree_buf::internal::Writable>::WriterArray }));
+
+    let buffers = fields.iter().map(|UnnamedField { ident, index, .. }| {
+        quote! {
+            self.#ident.buffer(&value.#index);
+        }
+    });
+
+    let flushers = fields.iter().map(|UnnamedField { ident, ty, .. }| {
+        quote! {
+            let o = self.#ident;
+            stream.write_with_id(|stream| ::tree_buf::internal::WriterArray::<#ty>::flush(o, stream));
+        }
+    });
+
+    let (prefix, suffix) = fields_type_id("Tuple", fields.len());
+
     let flush = quote! {
         #prefix
         #(#flushers)*
@@ -82,9 +135,50 @@ fn impl_struct_write(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
     fill_write_skeleton(ast, array_fields, buffer, flush, write_root)
 }
 
-fn fill_write_skeleton<A: ToTokens>(
+fn impl_unit_struct_write(ast: &DeriveInput) -> TokenStream {
+    let array_fields = std::iter::empty();
+
+    let buffer = quote! {
+        let _ = value;
+    };
+
+    let flush = quote! {
+        let _ = stream;
+        ::tree_buf::internal::ArrayTypeId::Void
+    };
+
+    let write_root = quote! {
+        let _ = stream;
+        ::tree_buf::internal::RootTypeId::Void
+    };
+
+    fill_write_skeleton(ast, array_fields, buffer, flush, write_root)
+}
+
+/// Writes the only field in place of the struct, without any wrapping.
+fn impl_transparent_write(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream {
+    let (member, ty) = get_transparent_field(data_struct);
+
+    let array_fields = std::iter::once((format_ident!("tree_buf_inner"), quote! { <#ty as ::tree_buf::internal::Writable>::WriterArray }));
+
+    let buffer = quote! {
+        self.tree_buf_inner.buffer(&value.#member);
+    };
+
+    let flush = quote! {
+        ::tree_buf::internal::WriterArray::<#ty>::flush(self.tree_buf_inner, stream)
+    };
+
+    let write_root = quote! {
+        self.#member.write_root(stream)
+    };
+
+    fill_write_skeleton(ast, array_fields, buffer, flush, write_root)
+}
+
+fn fill_write_skeleton(
     ast: &DeriveInput,
-    array_fields: impl Iterator<Item = A>,
+    array_fields: impl Iterator<Item = (Ident, TokenStream)>,
     buffer: impl ToTokens,
     flush: impl ToTokens,
     write_root: impl ToTokens,
@@ -92,16 +186,27 @@ fn fill_write_skeleton<A: ToTokens>(
     let name = &ast.ident;
     let vis = &ast.vis;
     let array_writer_name = format_ident!("{}TreeBufWriterArray", name);
+    let generics = add_bounds(&ast.generics, &[parse_quote!(::tree_buf::internal::Writable)]);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (field_idents, field_types): (Vec<_>, Vec<_>) = array_fields.unzip();
 
     quote! {
-        #[derive(Default)]
         #[allow(non_snake_case)]
-        #vis struct #array_writer_name {
-            #(#array_fields,)*
+        #vis struct #array_writer_name #impl_generics #where_clause {
+            #(#field_idents: #field_types,)*
+        }
+
+        // Not derived, because that would require the type parameters to be Default
+        impl #impl_generics ::std::default::Default for #array_writer_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#field_idents: ::std::default::Default::default(),)*
+                }
+            }
         }
 
-        impl ::tree_buf::internal::WriterArray<#name> for #array_writer_name {
-            fn buffer<'a, 'b : 'a>(&'a mut self, value: &'b #name) {
+        impl #impl_generics ::tree_buf::internal::WriterArray<#name #ty_generics> for #array_writer_name #ty_generics #where_clause {
+            fn buffer<'tree_buf_self, 'tree_buf_value : 'tree_buf_self>(&'tree_buf_self mut self, value: &'tree_buf_value #name #ty_generics) {
                 #buffer
             }
             fn flush<O: ::tree_buf::options::EncodeOptions>(mut self, stream: &mut ::tree_buf::internal::WriterStream<'_, O>) -> ::tree_buf::internal::ArrayTypeId {
@@ -112,8 +217,8 @@ fn fill_write_skeleton<A: ToTokens>(
             }
         }
 
-        impl ::tree_buf::internal::Writable for #name {
-            type WriterArray=#array_writer_name;
+        impl #impl_generics ::tree_buf::internal::Writable for #name #ty_generics #where_clause {
+            type WriterArray=#array_writer_name #ty_generics;
             fn write_root<O: ::tree_buf::options::EncodeOptions>(&self, stream: &mut ::tree_buf::internal::WriterStream<'_, O>) -> tree_buf::internal::RootTypeId {
                 // TODO: Re-enable profile here
                 // See also dcebaa54-d21e-4e79-abfe-4a89cc829180
@@ -131,11 +236,9 @@ fn impl_enum_write(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
     // A discriminant
 
     let mut array_fields = Vec::new();
-    array_fields.push(quote! {
-        // TODO: (Performance) have the size scale to the number of variants
-        tree_buf_discriminant: <u64 as ::tree_buf::Writable>::WriterArray,
-        tree_buf_next_discriminant: u64
-    });
+    // TODO: (Performance) have the size scale to the number of variants
+    array_fields.push((format_ident!("tree_buf_discriminant"), quote! { <u64 as ::tree_buf::Writable>::WriterArray }));
+    array_fields.push((format_ident!("tree_buf_next_discriminant"), quote! { u64 }));
 
     let mut array_matches = Vec::new();
     let mut root_matches = Vec::new();
@@ -147,18 +250,51 @@ fn impl_enum_write(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
         let discriminant = canonical_ident(variant_ident);
 
         match &variant.fields {
-            Fields::Unit => {
+            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) if unnamed.len() == 1 => {
+                let ty = &unnamed[0].ty;
                 root_matches.push(quote! {
-                    #ident::#variant_ident => {
+                    #ident::#variant_ident(_0) => {
                         ::tree_buf::internal::write_ident(#discriminant, stream);
-                        stream.write_with_id(|stream| ::tree_buf::internal::RootTypeId::Void);
+                        stream.write_with_id(|stream| _0.write_root(stream));
+                    }
+                });
+                array_fields.push((variant_ident.clone(), quote! { Option<(u64, <#ty as ::tree_buf::Writable>::WriterArray)> }));
+                array_matches.push(quote! {
+                    #ident::#variant_ident(_0) => {
+                        if self.#variant_ident.is_none() {
+                            self.#variant_ident = Some((self.tree_buf_next_discriminant, Default::default()));
+                            self.tree_buf_next_discriminant += 1;
+                        }
+                        let t = self.#variant_ident.as_mut().unwrap();
+                        self.tree_buf_discriminant.buffer(&t.0);
+                        t.1.buffer(_0);
                     }
                 });
-                array_fields.push(quote! {
-                    #variant_ident: Option<u64>
+                flushes.push(quote! {
+                    let mut matches = false;
+                    if let Some((d, _)) = &self.#variant_ident {
+                        if *d == current_discriminant {
+                            matches = true;
+                        }
+                    }
+                    if matches {
+                        let mut buffer = self.#variant_ident.take().unwrap().1;
+                        ::tree_buf::internal::write_ident(#discriminant, stream);
+                        stream.write_with_id(|stream| ::tree_buf::internal::WriterArray::<#ty>:: flush(buffer, stream));
+                        continue;
+                    }
                 });
+            }
+            fields if fields.iter().next().is_none() => {
+                root_matches.push(quote! {
+                    #ident::#variant_ident { .. } => {
+                        ::tree_buf::internal::write_ident(#discriminant, stream);
+                        stream.write_with_id(|stream| ::tree_buf::internal::RootTypeId::Void);
+                    }
+                });
+                array_fields.push((variant_ident.clone(), quote! { Option<u64> }));
                 array_matches.push(quote! {
-                    #ident::#variant_ident => {
+                    #ident::#variant_ident { .. } => {
                         let t = if let Some(t) = self.#variant_ident {
                             t
                         } else {
@@ -184,52 +320,73 @@ fn impl_enum_write(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
                     }
                 });
             }
-            Fields::Named(_named_fields) => todo!("Enums with named fields not yet supported by tree-buf"),
-            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-                let unnamed: Vec<_> = unnamed.iter().collect();
-                match unnamed.len() {
-                    // TODO: Check if this is really unreachable. It might be `Variant {}`.
-                    // In this case consider writing a struct with no fields
-                    0 => unreachable!(),
-                    1 => {
-                        let ty = &unnamed[0].ty;
-                        root_matches.push(quote! {
-                            #ident::#variant_ident(_0) => {
-                                ::tree_buf::internal::write_ident(#discriminant, stream);
-                                stream.write_with_id(|stream| _0.write_root(stream));
-                            }
-                        });
-                        array_fields.push(quote! {
-                            #variant_ident: Option<(u64, <#ty as ::tree_buf::Writable>::WriterArray)>
-                        });
-                        array_matches.push(quote! {
-                            #ident::#variant_ident(_0) => {
-                                if self.#variant_ident.is_none() {
-                                    self.#variant_ident = Some((self.tree_buf_next_discriminant, Default::default()));
-                                    self.tree_buf_next_discriminant += 1;
-                                }
-                                let t = self.#variant_ident.as_mut().unwrap();
-                                self.tree_buf_discriminant.buffer(&t.0);
-                                t.1.buffer(_0);
-                            }
+            // Named fields and multiple unnamed fields are written as an object or tuple
+            fields => {
+                let fields = get_variant_fields(fields);
+                let kind = if fields[0].canon_str.is_some() { "Obj" } else { "Tuple" };
+                let (prefix, suffix) = fields_type_id(kind, fields.len());
+                let bindings = fields.iter().map(|VariantField { member, binding, .. }| quote! { #member: #binding }).collect::<Vec<_>>();
+
+                let root_writers = fields.iter().map(|VariantField { binding, canon_str, .. }| {
+                    let write_ident = canon_str.as_ref().map(|canon_str| quote! { ::tree_buf::internal::write_ident(#canon_str, stream); });
+                    quote! {
+                        #write_ident
+                        stream.write_with_id(|stream| #binding.write_root(stream));
+                    }
+                });
+                root_matches.push(quote! {
+                    #ident::#variant_ident { #(#bindings),* } => {
+                        ::tree_buf::internal::write_ident(#discriminant, stream);
+                        stream.write_with_id(|stream| {
+                            #prefix
+                            #(#root_writers)*
+                            ::tree_buf::internal::RootTypeId::#suffix
                         });
-                        flushes.push(quote! {
-                            let mut matches = false;
-                            if let Some((d, _)) = &self.#variant_ident {
-                                if *d == current_discriminant {
-                                    matches = true;
-                                }
-                            }
-                            if matches {
-                                let mut buffer = self.#variant_ident.take().unwrap().1;
-                                ::tree_buf::internal::write_ident(#discriminant, stream);
-                                stream.write_with_id(|stream| ::tree_buf::internal::WriterArray::<#ty>:: flush(buffer, stream));
-                                continue;
-                            }
+                    }
+                });
+
+                let writer_arrays = fields.iter().map(|VariantField { ty, .. }| quote! { <#ty as ::tree_buf::Writable>::WriterArray });
+                array_fields.push((variant_ident.clone(), quote! { Option<(u64, (#(#writer_arrays,)*))> }));
+
+                let buffers = fields.iter().map(|VariantField { binding, index, .. }| quote! { t.1.#index.buffer(#binding); });
+                array_matches.push(quote! {
+                    #ident::#variant_ident { #(#bindings),* } => {
+                        if self.#variant_ident.is_none() {
+                            self.#variant_ident = Some((self.tree_buf_next_discriminant, Default::default()));
+                            self.tree_buf_next_discriminant += 1;
+                        }
+                        let t = self.#variant_ident.as_mut().unwrap();
+                        self.tree_buf_discriminant.buffer(&t.0);
+                        #(#buffers)*
+                    }
+                });
+
+                let flushers = fields.iter().map(|VariantField { index, ty, canon_str, .. }| {
+                    let write_ident = canon_str.as_ref().map(|canon_str| quote! { ::tree_buf::internal::write_ident(#canon_str, stream); });
+                    quote! {
+                        #write_ident
+                        let o = buffer.#index;
+                        stream.write_with_id(|stream| ::tree_buf::internal::WriterArray::<#ty>::flush(o, stream));
+                    }
+                });
+                flushes.push(quote! {
+                    let mut matches = false;
+                    if let Some((d, _)) = &self.#variant_ident {
+                        if *d == current_discriminant {
+                            matches = true;
+                        }
+                    }
+                    if matches {
+                        let buffer = self.#variant_ident.take().unwrap().1;
+                        ::tree_buf::internal::write_ident(#discriminant, stream);
+                        stream.write_with_id(|stream| {
+                            #prefix
+                            #(#flushers)*
+                            ::tree_buf::internal::ArrayTypeId::#suffix
                         });
+                        continue;
                     }
-                    _ => todo!("Enums with multiple unnamed fields not yet supported by tree-buf Write"),
-                }
+                });
             }
         }
     }
@@ -258,5 +415,5 @@ fn impl_enum_write(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
         ::tree_buf::internal::ArrayTypeId::Enum
     };
 
-    fill_write_skeleton(ast, array_fields.iter(), buffer, flush, write_root)
+    fill_write_skeleton(ast, array_fields.into_iter(), buffer, flush, write_root)
 }
@@ -126,11 +126,17 @@ fn visit_array(path: Path, branch: &DynArrayBranch, breakdown: &mut SizeBreakdow
             ArrayFloat::F64(b) => breakdown.add(&path, "Fixed F64", b),
             ArrayFloat::Zfp32(b) => breakdown.add(&path, "Zfp 64", b),
             ArrayFloat::Zfp64(b) => breakdown.add(&path, "Zfp 32", b),
+            ArrayFloat::ByteStreamSplit32(b) => breakdown.add(&path, "Byte Stream Split F32", b),
+            ArrayFloat::ByteStreamSplit64(b) => breakdown.add(&path, "Byte Stream Split F64", b),
         },
         DynArrayBranch::Integer(ArrayInteger { bytes, encoding }) => match encoding {
             ArrayIntegerEncoding::PrefixVarInt => breakdown.add(&path, "Prefix Varint", bytes),
             ArrayIntegerEncoding::Simple16 => breakdown.add(&path, "Simple16", bytes),
             ArrayIntegerEncoding::U8 => breakdown.add(&path, "U8 Fixed", bytes),
+            ArrayIntegerEncoding::BitPacked => breakdown.add(&path, "Bit Packed", bytes),
+            ArrayIntegerEncoding::FrameOfReference => breakdown.add(&path, "Frame of Reference", bytes),
+            ArrayIntegerEncoding::PatchedFrameOfReference => breakdown.add(&path, "Patched Frame of Reference", bytes),
+            ArrayIntegerEncoding::Simple8b => breakdown.add(&path, "Simple8b", bytes),
         },
         DynArrayBranch::Map { len, keys, values } => {
             visit_array(path.a(&"len", &"Map"), len, breakdown);
@@ -150,7 +156,24 @@ fn visit_array(path: Path, branch: &DynArrayBranch, breakdown: &mut SizeBreakdow
             visit_array(path.a(&"indices", &"Dictionary"), indices, breakdown);
             visit_array(path.a(&"values", &"Dictionary"), values, breakdown);
         }
+        DynArrayBranch::Zigzag { values } => visit_array(path.a(&"values", &"Zigzag"), values, breakdown),
+        DynArrayBranch::Delta { order, len: _, values } => visit_array(path.a(&"values", &format!("Delta{}", order)), values, breakdown),
+        DynArrayBranch::U128 { hi, lo } => {
+            visit_array(path.a(&"hi", &"U128"), hi, breakdown);
+            visit_array(path.a(&"lo", &"U128"), lo, breakdown);
+        }
         DynArrayBranch::String(b) => breakdown.add(&path, "UTF-8", b),
+        DynArrayBranch::Blob { len, bytes } => {
+            visit_array(path.a(&"len", &"Blob"), len, breakdown);
+            match bytes {
+                BlobBytes::Raw(b) => breakdown.add(&path, "Blob", b),
+                BlobBytes::Lz4 { len: _, compressed } => breakdown.add(&path, "Blob LZ4", compressed),
+            }
+        }
+        DynArrayBranch::Decimal { mantissa, scale } => {
+            visit_array(path.a(&"mantissa", &"Decimal"), mantissa, breakdown);
+            visit_array(path.a(&"scale", &"Decimal"), scale, breakdown);
+        }
         DynArrayBranch::Tuple { fields } => {
             for (i, field) in fields.iter().enumerate() {
                 visit_array(path.a(&i, &"Tuple"), field, breakdown);
@@ -187,18 +210,20 @@ fn visit(path: Path, branch: &DynRootBranch<'_>, breakdown: &mut SizeBreakdown)
         }
         DynRootBranch::Array { len, values } => visit_array(path.a(&format!("[{}]", len), &"Array"), values, breakdown),
         DynRootBranch::Array1(item) => visit(path.a(&"1", &"Array1"), item, breakdown),
+        DynRootBranch::Decimal { mantissa, scale: _ } => visit(path.a(&"mantissa", &"Decimal"), mantissa, breakdown),
         DynRootBranch::Boolean(_)
         | DynRootBranch::Array0
         | DynRootBranch::Map0
         | DynRootBranch::Void
         | DynRootBranch::Float(_)
         | DynRootBranch::Integer(_)
-        | DynRootBranch::String(_) => {}
+        | DynRootBranch::String(_)
+        | DynRootBranch::Blob(_) => {}
     }
 }
 
 pub fn size_breakdown(data: &[u8]) -> ReadResult<String> {
-    let root = read_root(data)?;
+    let root = read_root(data, &DecodeOptionsDefault)?;
 
     let mut breakdown = SizeBreakdown {
         by_path: HashMap::new(),
@@ -1,5 +1,10 @@
+use super::{check_count, read_field_count};
+#[cfg(feature = "read")]
+use crate::internal::encodings::lz4;
 use crate::internal::encodings::varint::*;
 use crate::prelude::*;
+#[cfg(feature = "read")]
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
@@ -21,6 +26,15 @@ impl<'a> From<&'a [u8]> for Bytes<'a> {
     }
 }
 
+impl<'a> Bytes<'a> {
+    /// Unlike `Deref`, the slice returned may outlive `self`,
+    /// which allows values to borrow from the original bytes.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
 impl Deref for Bytes<'_> {
     type Target = [u8];
     #[inline]
@@ -36,6 +50,8 @@ pub enum ArrayFloat<'a> {
     DoubleGorilla(Bytes<'a>),
     Zfp32(Bytes<'a>),
     Zfp64(Bytes<'a>),
+    ByteStreamSplit32(Bytes<'a>),
+    ByteStreamSplit64(Bytes<'a>),
 }
 
 #[derive(Debug)]
@@ -44,6 +60,24 @@ pub enum ArrayBool<'a> {
     RLE(bool, Box<DynArrayBranch<'a>>),
 }
 
+/// The bytes of one or more blobs, which may have been compressed as a whole.
+#[derive(Debug)]
+pub enum BlobBytes<'a> {
+    Raw(Bytes<'a>),
+    Lz4 { len: usize, compressed: Bytes<'a> },
+}
+
+#[cfg(feature = "read")]
+impl<'a> BlobBytes<'a> {
+    /// Raw bytes are borrowed rather than copied.
+    pub fn decompress(&self, limits: &Limits) -> ReadResult<Cow<'a, [u8]>> {
+        match self {
+            BlobBytes::Raw(bytes) => Ok(Cow::Borrowed(bytes.as_slice())),
+            BlobBytes::Lz4 { len, compressed } => Ok(Cow::Owned(lz4::decompress(compressed, limits.allocation(*len)?)?)),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ArrayEnumVariant<'a> {
     pub ident: Ident<'a>,
@@ -94,6 +128,33 @@ pub enum DynArrayBranch<'a> {
         indices: Box<DynArrayBranch<'a>>,
         values: Box<DynArrayBranch<'a>>,
     },
+    Zigzag {
+        values: Box<DynArrayBranch<'a>>,
+    },
+    /// Zigzag encoded differences between consecutive integers.
+    /// An order of 2 is the deltas of the deltas.
+    Delta {
+        order: u8,
+        // Some encodings pad the end with zeros, which would
+        // otherwise decode as more values instead of defaults.
+        len: usize,
+        values: Box<DynArrayBranch<'a>>,
+    },
+    /// Integers which don't fit in 64 bits, split into columns
+    /// of the high and low 64 bits of each value.
+    U128 {
+        hi: Box<DynArrayBranch<'a>>,
+        lo: Box<DynArrayBranch<'a>>,
+    },
+    /// The bytes of all blobs are concatenated.
+    Blob {
+        len: Box<DynArrayBranch<'a>>,
+        bytes: BlobBytes<'a>,
+    },
+    Decimal {
+        mantissa: Box<DynArrayBranch<'a>>,
+        scale: Box<DynArrayBranch<'a>>,
+    },
     // TODO:
     // In any array context, we can have a 'dynamic' value, which resolves to an array of DynRootBranch (like a nested file)
     // This generally should not be used, but the existance of it is an escape hatch bringing the capability to use truly unstructured
@@ -101,117 +162,204 @@ pub enum DynArrayBranch<'a> {
     // Dynamic(Bytes<'a>)
 }
 
-pub fn read_next_array<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<DynArrayBranch<'a>> {
+pub fn read_next_array<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynArrayBranch<'a>> {
+    let limits = limits.nested()?;
     let id = ArrayTypeId::read_next(bytes, offset)?;
 
     use ArrayTypeId::*;
 
-    fn read_ints<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, encoding: ArrayIntegerEncoding) -> ReadResult<DynArrayBranch<'a>> {
-        let bytes = read_bytes_from_len(bytes, offset, lens)?.into();
-        Ok(DynArrayBranch::Integer(ArrayInteger { bytes, encoding }))
-    }
-
-    fn read_bytes_from_len<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<Bytes<'a>> {
-        let len = decode_suffix_varint(bytes, lens)?;
-        Ok(read_bytes(len as usize, bytes, offset)?.into())
-    }
-
     // See also e25db64d-8424-46b9-bdc1-cdb618807513
-    fn read_tuple<'a>(num_fields: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<DynArrayBranch<'a>> {
-        let mut fields = Vec::with_capacity(num_fields);
+    fn read_tuple<'a>(num_fields: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynArrayBranch<'a>> {
+        let mut fields = Vec::with_capacity(check_count(num_fields, bytes, *offset)?);
         for _ in 0..num_fields {
-            let child = read_next_array(bytes, offset, lens)?;
+            let child = read_next_array(bytes, offset, lens, limits)?;
             fields.push(child);
         }
         Ok(DynArrayBranch::Tuple { fields })
     }
 
     // See also 47a1482f-5ce3-4b78-b356-30c66dc60cda
-    fn read_obj<'a>(num_fields: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<DynArrayBranch<'a>> {
-        let mut fields = HashMap::with_capacity(num_fields);
+    fn read_obj<'a>(num_fields: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynArrayBranch<'a>> {
+        let mut fields = HashMap::with_capacity(check_count(num_fields, bytes, *offset)?);
         for _ in 0..num_fields {
             let name = crate::internal::read_ident(bytes, offset)?;
-            let child = read_next_array(bytes, offset, lens)?;
+            let child = read_next_array(bytes, offset, lens, limits)?;
             fields.insert(name, child);
         }
         Ok(DynArrayBranch::Object { fields })
     }
 
-    let branch = match id {
+    fn read_child<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<Box<DynArrayBranch<'a>>> {
+        Ok(Box::new(read_next_array(bytes, offset, lens, limits)?))
+    }
+
+    fn read_enum<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynArrayBranch<'a>> {
+        let count = check_count(read_usize(bytes, offset)?, bytes, *offset)?;
+        let mut variants = Vec::with_capacity(count);
+
+        // TODO: Elide discriminants when there are 0 or 1 variants
+        let discriminants = read_child(bytes, offset, lens, limits)?;
+
+        if count != 0 {
+            for _ in 0..count {
+                variants.push(ArrayEnumVariant {
+                    ident: read_ident(bytes, offset)?,
+                    data: read_next_array(bytes, offset, lens, limits)?,
+                });
+            }
+        }
+
+        Ok(DynArrayBranch::Enum { discriminants, variants })
+    }
+
+    // Each arm results in a ReadResult rather than using ? on a branch so that in debug builds
+    // the arms don't each reserve stack space for one, which limits how deeply documents may be nested.
+    match id {
         Nullable => {
-            let opt = read_next_array(bytes, offset, lens)?.into();
-            let values = read_next_array(bytes, offset, lens)?.into();
-            DynArrayBranch::Nullable { opt, values }
+            let opt = read_child(bytes, offset, lens, limits)?;
+            let values = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::Nullable { opt, values })
         }
-        Void => DynArrayBranch::Void,
-        Tuple2 => read_tuple(2, bytes, offset, lens)?,
-        Tuple3 => read_tuple(3, bytes, offset, lens)?,
-        Tuple4 => read_tuple(4, bytes, offset, lens)?,
-        Tuple5 => read_tuple(5, bytes, offset, lens)?,
-        Tuple6 => read_tuple(6, bytes, offset, lens)?,
-        Tuple7 => read_tuple(7, bytes, offset, lens)?,
-        Tuple8 => read_tuple(8, bytes, offset, lens)?,
-        TupleN => read_tuple(decode_prefix_varint(bytes, offset)? as usize + 9, bytes, offset, lens)?,
+        Void => Ok(DynArrayBranch::Void),
+        Tuple1 => read_tuple(1, bytes, offset, lens, limits),
+        Tuple2 => read_tuple(2, bytes, offset, lens, limits),
+        Tuple3 => read_tuple(3, bytes, offset, lens, limits),
+        Tuple4 => read_tuple(4, bytes, offset, lens, limits),
+        Tuple5 => read_tuple(5, bytes, offset, lens, limits),
+        Tuple6 => read_tuple(6, bytes, offset, lens, limits),
+        Tuple7 => read_tuple(7, bytes, offset, lens, limits),
+        Tuple8 => read_tuple(8, bytes, offset, lens, limits),
+        TupleN => read_tuple(read_field_count(bytes, offset)?, bytes, offset, lens, limits),
         ArrayVar => {
-            let len = read_next_array(bytes, offset, lens)?;
-            match len {
-                DynArrayBranch::Void => DynArrayBranch::Array0,
+            let len = read_child(bytes, offset, lens, limits)?;
+            match *len {
+                DynArrayBranch::Void => Ok(DynArrayBranch::Array0),
                 _ => {
                     // FIXME: Verify that len is Integer here. If not, the file is invalid.
                     // This may not be verified later if the schema is selectively matched.
-                    let len = Box::new(len);
-                    let values = read_next_array(bytes, offset, lens)?;
-                    let values = Box::new(values);
-                    DynArrayBranch::Array { len, values }
+                    let values = read_child(bytes, offset, lens, limits)?;
+                    Ok(DynArrayBranch::Array { len, values })
                 }
             }
         }
         ArrayFixed => {
-            let len = read_usize(bytes, offset)?;
-            let values = read_next_array(bytes, offset, lens)?;
-            let values = Box::new(values);
-            DynArrayBranch::ArrayFixed { len, values }
+            let len = limits.length(read_usize(bytes, offset)?)?;
+            let values = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::ArrayFixed { len, values })
         }
         Map => {
-            let len = read_next_array(bytes, offset, lens)?;
-            match len {
-                DynArrayBranch::Void => DynArrayBranch::Map0,
+            let len = read_child(bytes, offset, lens, limits)?;
+            match *len {
+                DynArrayBranch::Void => Ok(DynArrayBranch::Map0),
                 _ => {
                     // FIXME: Verify that len is Integer here. If not, the file is invalid.
                     // This may not be verified later if the schema is selectively matched.
-                    let len = Box::new(len);
-                    let keys = read_next_array(bytes, offset, lens)?;
-                    let keys = Box::new(keys);
-                    let values = read_next_array(bytes, offset, lens)?;
-                    let values = Box::new(values);
-                    DynArrayBranch::Map { len, keys, values }
+                    let keys = read_child(bytes, offset, lens, limits)?;
+                    let values = read_child(bytes, offset, lens, limits)?;
+                    Ok(DynArrayBranch::Map { len, keys, values })
                 }
             }
         }
 
         // See also: fadaec14-35ad-4dc1-b6dc-6106ab811669
-        Obj0 => read_obj(0, bytes, offset, lens)?,
-        Obj1 => read_obj(1, bytes, offset, lens)?,
-        Obj2 => read_obj(2, bytes, offset, lens)?,
-        Obj3 => read_obj(3, bytes, offset, lens)?,
-        Obj4 => read_obj(4, bytes, offset, lens)?,
-        Obj5 => read_obj(5, bytes, offset, lens)?,
-        Obj6 => read_obj(6, bytes, offset, lens)?,
-        Obj7 => read_obj(7, bytes, offset, lens)?,
-        Obj8 => read_obj(8, bytes, offset, lens)?,
-        ObjN => read_obj(decode_prefix_varint(bytes, offset)? as usize + 9, bytes, offset, lens)?,
+        Obj0 => read_obj(0, bytes, offset, lens, limits),
+        Obj1 => read_obj(1, bytes, offset, lens, limits),
+        Obj2 => read_obj(2, bytes, offset, lens, limits),
+        Obj3 => read_obj(3, bytes, offset, lens, limits),
+        Obj4 => read_obj(4, bytes, offset, lens, limits),
+        Obj5 => read_obj(5, bytes, offset, lens, limits),
+        Obj6 => read_obj(6, bytes, offset, lens, limits),
+        Obj7 => read_obj(7, bytes, offset, lens, limits),
+        Obj8 => read_obj(8, bytes, offset, lens, limits),
+        ObjN => read_obj(read_field_count(bytes, offset)?, bytes, offset, lens, limits),
+        RLEBoolTrue | RLEBoolFalse => {
+            let first = matches!(id, ArrayTypeId::RLEBoolTrue);
+            let runs = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::Boolean(ArrayBool::RLE(first, runs)))
+        }
+        Enum => read_enum(bytes, offset, lens, limits),
+        RLE => {
+            let values = read_child(bytes, offset, lens, limits)?;
+            let runs = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::RLE { runs, values })
+        }
+        Dictionary => {
+            let values = read_child(bytes, offset, lens, limits)?;
+            let indices = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::Dictionary { values, indices })
+        }
+        Zigzag => {
+            let values = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::Zigzag { values })
+        }
+        Delta => {
+            let len = read_usize(bytes, offset)?;
+            let values = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::Delta { order: 1, len, values })
+        }
+        DeltaOfDelta => {
+            let len = read_usize(bytes, offset)?;
+            let values = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::Delta { order: 2, len, values })
+        }
+        U128 => {
+            let hi = read_child(bytes, offset, lens, limits)?;
+            let lo = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::U128 { hi, lo })
+        }
+        Blob => {
+            let len = read_child(bytes, offset, lens, limits)?;
+            let bytes = BlobBytes::Raw(read_bytes_from_len(bytes, offset, lens)?);
+            Ok(DynArrayBranch::Blob { len, bytes })
+        }
+        BlobLz4 => {
+            let len = read_child(bytes, offset, lens, limits)?;
+            let decompressed_len = read_usize(bytes, offset)?;
+            let compressed = read_bytes_from_len(bytes, offset, lens)?;
+            Ok(DynArrayBranch::Blob {
+                len,
+                bytes: BlobBytes::Lz4 {
+                    len: decompressed_len,
+                    compressed,
+                },
+            })
+        }
+        Decimal => {
+            let mantissa = read_child(bytes, offset, lens, limits)?;
+            let scale = read_child(bytes, offset, lens, limits)?;
+            Ok(DynArrayBranch::Decimal { mantissa, scale })
+        }
+        _ => read_array_scalar(id, bytes, offset, lens),
+    }
+}
+
+fn read_bytes_from_len<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<Bytes<'a>> {
+    let len = decode_suffix_varint(bytes, lens)?;
+    Ok(read_bytes(len as usize, bytes, offset)?.into())
+}
+
+/// Reads the branches which don't have children. These are separate from read_next_array
+/// to keep its stack frame small, since it is called once for each level of nesting.
+#[inline(never)]
+fn read_array_scalar<'a>(id: ArrayTypeId, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<DynArrayBranch<'a>> {
+    fn read_ints<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, encoding: ArrayIntegerEncoding) -> ReadResult<DynArrayBranch<'a>> {
+        let bytes = read_bytes_from_len(bytes, offset, lens)?.into();
+        Ok(DynArrayBranch::Integer(ArrayInteger { bytes, encoding }))
+    }
+
+    use ArrayTypeId::*;
+    let branch = match id {
         PackedBool => {
             let bytes = read_bytes_from_len(bytes, offset, lens)?;
             DynArrayBranch::Boolean(ArrayBool::Packed(bytes))
         }
-        RLEBoolTrue | RLEBoolFalse => {
-            let first = matches!(id, ArrayTypeId::RLEBoolTrue);
-            let runs = read_next_array(bytes, offset, lens)?;
-            DynArrayBranch::Boolean(ArrayBool::RLE(first, runs.into()))
-        }
         IntSimple16 => read_ints(bytes, offset, lens, ArrayIntegerEncoding::Simple16)?,
         IntPrefixVar => read_ints(bytes, offset, lens, ArrayIntegerEncoding::PrefixVarInt)?,
         U8 => read_ints(bytes, offset, lens, ArrayIntegerEncoding::U8)?,
+        IntBitPacked => read_ints(bytes, offset, lens, ArrayIntegerEncoding::BitPacked)?,
+        IntFrameOfReference => read_ints(bytes, offset, lens, ArrayIntegerEncoding::FrameOfReference)?,
+        IntPatchedFrameOfReference => read_ints(bytes, offset, lens, ArrayIntegerEncoding::PatchedFrameOfReference)?,
+        IntSimple8b => read_ints(bytes, offset, lens, ArrayIntegerEncoding::Simple8b)?,
         F32 => {
             let bytes = read_bytes_from_len(bytes, offset, lens)?;
             DynArrayBranch::Float(ArrayFloat::F32(bytes))
@@ -236,39 +384,45 @@ pub fn read_next_array<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut
             let bytes = read_bytes_from_len(bytes, offset, lens)?;
             DynArrayBranch::Float(ArrayFloat::DoubleGorilla(bytes))
         }
-        Enum => {
-            let count = decode_prefix_varint(bytes, offset)? as usize;
-            let mut variants = Vec::with_capacity(count);
-
-            // TODO: Elide discriminants when there are 0 or 1 variants
-            let discriminants = read_next_array(bytes, offset, lens)?.into();
-
-            if count != 0 {
-                for _ in 0..count {
-                    variants.push(ArrayEnumVariant {
-                        ident: read_ident(bytes, offset)?,
-                        data: read_next_array(bytes, offset, lens)?,
-                    });
-                }
-            }
-
-            DynArrayBranch::Enum { discriminants, variants }
-        }
-        RLE => {
-            let values = read_next_array(bytes, offset, lens)?.into();
-            let runs = read_next_array(bytes, offset, lens)?.into();
-            DynArrayBranch::RLE { runs, values }
+        ByteStreamSplit32 => {
+            let bytes = read_bytes_from_len(bytes, offset, lens)?;
+            DynArrayBranch::Float(ArrayFloat::ByteStreamSplit32(bytes))
         }
-        Dictionary => {
-            let values = read_next_array(bytes, offset, lens)?.into();
-            let indices = read_next_array(bytes, offset, lens)?.into();
-            DynArrayBranch::Dictionary { values, indices }
+        ByteStreamSplit64 => {
+            let bytes = read_bytes_from_len(bytes, offset, lens)?;
+            DynArrayBranch::Float(ArrayFloat::ByteStreamSplit64(bytes))
         }
+        _ => unreachable!("Branches with children are read by read_next_array"),
     };
-
     Ok(branch)
 }
 
+impl<'a> DynArrayBranch<'a> {
+    /// The kind of value in the document, for error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            DynArrayBranch::Object { .. } => "Object",
+            DynArrayBranch::Tuple { .. } => "Tuple",
+            DynArrayBranch::Array0 | DynArrayBranch::Array { .. } | DynArrayBranch::ArrayFixed { .. } => "Array",
+            DynArrayBranch::Map0 | DynArrayBranch::Map { .. } => "Map",
+            DynArrayBranch::Integer(_) => "Integer",
+            DynArrayBranch::Nullable { .. } => "Nullable",
+            DynArrayBranch::Boolean(_) => "Boolean",
+            DynArrayBranch::Float(_) => "Float",
+            DynArrayBranch::Void => "Void",
+            DynArrayBranch::String(_) => "String",
+            DynArrayBranch::Enum { .. } => "Enum",
+            DynArrayBranch::RLE { .. } => "RLE",
+            DynArrayBranch::Dictionary { .. } => "Dictionary",
+            DynArrayBranch::Zigzag { .. } => "Zigzag",
+            DynArrayBranch::Delta { .. } => "Delta",
+            DynArrayBranch::U128 { .. } => "Integer",
+            DynArrayBranch::Blob { .. } => "Blob",
+            DynArrayBranch::Decimal { .. } => "Decimal",
+        }
+    }
+}
+
 impl<'a> Default for DynArrayBranch<'a> {
     fn default() -> Self {
         DynArrayBranch::Void
@@ -295,13 +449,24 @@ impl_type_id!(ArrayTypeId, [
     Dictionary: 17,
     RLEBoolTrue: 18,
     RLEBoolFalse: 19,
+    Zigzag: 20,
+    U128: 21,
+    Blob: 22,
+    BlobLz4: 23,
+    Decimal: 24,
+    Delta: 25,
+    DeltaOfDelta: 26,
+    IntBitPacked: 27,
+    IntFrameOfReference: 28,
+    IntPatchedFrameOfReference: 29,
+    IntSimple8b: 30,
+    ByteStreamSplit32: 31,
+    ByteStreamSplit64: 32,
 ]);
 
 #[derive(Debug)]
 pub struct ArrayInteger<'a> {
     pub bytes: Bytes<'a>,
-    //delta: bool,
-    //zigzag: bool,
     pub encoding: ArrayIntegerEncoding,
 }
 
@@ -310,4 +475,8 @@ pub enum ArrayIntegerEncoding {
     PrefixVarInt,
     Simple16,
     U8,
+    BitPacked,
+    FrameOfReference,
+    PatchedFrameOfReference,
+    Simple8b,
 }
@@ -23,6 +23,7 @@ macro_rules! impl_type_id {
             Obj7: 115,
             Obj8: 116,
             ObjN: 117,
+            Tuple1: 118,
             $($name: $i,)+
         ]);
     };
@@ -95,9 +96,6 @@ pub trait TypeId: Copy + Into<u8> + PartialEq + std::fmt::Debug {
         Self: Sized;
 }
 
-// TODO: Finish or get rid of this mod
-//mod visitor;
-
 // TODO: There are conceptually 4 pieces which are intermingled in this code.
 // 1: The actual 'object model' that TreeBuf uses. Eg:
 //     Root values, array values,
@@ -116,14 +114,32 @@ pub trait TypeId: Copy + Into<u8> + PartialEq + std::fmt::Debug {
 // Note that the object model may be just defined in terms of eg: Number, where Number is the sum type of F64, u64, and i64 with downcasts.
 
 #[cfg(feature = "read")]
-pub fn read_root(bytes: &[u8]) -> ReadResult<DynRootBranch<'_>> {
+pub fn read_root<'a>(bytes: &'a [u8], options: &impl DecodeOptions) -> ReadResult<DynRootBranch<'a>> {
     profile!(&[u8], "read_root");
-    if bytes.len() == 0 {
+    let mut offset = 0;
+    read_header(bytes, &mut offset)?;
+    if bytes.len() == offset {
         return Ok(DynRootBranch::Void);
     }
     let mut lens = bytes.len() - 1;
-    let mut offset = 0;
-    read_next_root(bytes, &mut offset, &mut lens)
+    read_next_root(bytes, &mut offset, &mut lens, Limits::new(options)).map_err(|e| e.at_offset(offset))
+}
+
+/// Every field or variant takes at least one byte, so a count larger than the remaining
+/// bytes can only come from a corrupt document. Checking this before allocating space
+/// for them prevents a small document from causing a large allocation.
+#[cfg(feature = "read")]
+pub(crate) fn check_count(count: usize, bytes: &[u8], offset: usize) -> ReadResult<usize> {
+    if count > bytes.len().saturating_sub(offset) {
+        return Err(ReadError::InvalidFormat);
+    }
+    Ok(count)
+}
+
+/// TupleN and ObjN are only used for 9 or more fields.
+#[cfg(feature = "read")]
+pub(crate) fn read_field_count(bytes: &[u8], offset: &mut usize) -> ReadResult<usize> {
+    read_usize(bytes, offset)?.checked_add(9).ok_or(ReadError::InvalidFormat)
 }
 
 #[cfg(test)]
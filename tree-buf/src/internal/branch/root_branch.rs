@@ -1,4 +1,4 @@
-use crate::internal::encodings::varint::decode_prefix_varint;
+use super::{check_count, read_field_count};
 use crate::prelude::*;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
@@ -14,7 +14,6 @@ use std::convert::{TryFrom, TryInto};
 
 // TODO: GUID. Consider having some sort of "semantic" flag to denote other kinds of values (like f64/u64 -> timestamp/date, 128 bit [u8] -> GUID)
 // TODO: Other kinds of self-description may also be interesting, since this is for data self-description is higher value
-// TODO: Bytes/Blog = [u8] compressed (eg: gzip), uncompressed
 
 // TODO: Try each compression on a sample of the data (first 1024 or so?) in turn to decide which to use.
 // 8-Array & different fixed/variable sizes - 0,1,2,128,custom(follows). Fixed 0 necessarily has Void child
@@ -53,110 +52,140 @@ pub enum DynRootBranch<'a> {
         keys: DynArrayBranch<'a>,
         values: DynArrayBranch<'a>,
     },
+    Blob(BlobBytes<'a>),
+    Decimal {
+        mantissa: Box<DynRootBranch<'a>>,
+        scale: u32,
+    },
 }
 
-pub fn read_next_root<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<DynRootBranch<'a>> {
+pub fn read_next_root<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynRootBranch<'a>> {
+    let limits = limits.nested()?;
     let id = RootTypeId::read_next(bytes, offset)?;
 
     // See also e25db64d-8424-46b9-bdc1-cdb618807513
-    fn read_tuple<'a>(num_fields: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<DynRootBranch<'a>> {
-        let mut fields = Vec::with_capacity(num_fields);
+    fn read_tuple<'a>(num_fields: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynRootBranch<'a>> {
+        let mut fields = Vec::with_capacity(check_count(num_fields, bytes, *offset)?);
         for _ in 0..num_fields {
-            let child = read_next_root(bytes, offset, lens)?;
+            let child = read_next_root(bytes, offset, lens, limits)?;
             fields.push(child);
         }
         Ok(DynRootBranch::Tuple { fields })
     }
 
-    fn read_array<'a>(len: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<DynRootBranch<'a>> {
-        let values = read_next_array(bytes, offset, lens)?;
+    fn read_array<'a>(len: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynRootBranch<'a>> {
+        let values = read_next_array(bytes, offset, lens, limits)?;
         Ok(DynRootBranch::Array { len, values })
     }
 
     // See also 47a1482f-5ce3-4b78-b356-30c66dc60cda
-    fn read_obj<'a>(num_fields: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize) -> ReadResult<DynRootBranch<'a>> {
-        let mut fields = HashMap::with_capacity(num_fields);
+    fn read_obj<'a>(num_fields: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynRootBranch<'a>> {
+        let mut fields = HashMap::with_capacity(check_count(num_fields, bytes, *offset)?);
         for _ in 0..num_fields {
             let name = crate::internal::read_ident(bytes, offset)?;
-            let child = read_next_root(bytes, offset, lens)?;
+            let child = read_next_root(bytes, offset, lens, limits)?;
             fields.insert(name, child);
         }
         Ok(DynRootBranch::Object { fields })
     }
 
-    fn read_str<'a>(len: usize, bytes: &'a [u8], offset: &'_ mut usize) -> ReadResult<DynRootBranch<'a>> {
-        let bytes = read_bytes(len, bytes, offset)?;
-        let s = std::str::from_utf8(bytes)?;
-        Ok(DynRootBranch::String(s))
+    fn read_map<'a>(len: usize, bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynRootBranch<'a>> {
+        let branch = match len {
+            0 => DynRootBranch::Map0,
+            1 => {
+                let key = read_next_root(bytes, offset, lens, limits)?.into();
+                let value = read_next_root(bytes, offset, lens, limits)?.into();
+                DynRootBranch::Map1 { key, value }
+            }
+            _ => {
+                let keys = read_next_array(bytes, offset, lens, limits)?;
+                let values = read_next_array(bytes, offset, lens, limits)?;
+                DynRootBranch::Map { len, keys, values }
+            }
+        };
+        Ok(branch)
+    }
+
+    fn read_enum<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynRootBranch<'a>> {
+        // TODO: Consider having the enum be:
+        //    Root: static_data: RootBranch, instance_data: RootBranch
+        //    Array: static_data: RootBranch, instance_data: ArrayBranch, discriminant: ArrayBranch(int)
+        // The interesting idea here is that it can be more powerful in that it supports complex value de-duplication
+        // Eg: like a primary key. One could represent more information (eg: Like a politician having a name and party
+        // when having a table for primaries and delegates). That might be more interesting as some kind of pointer type.
+        // It also would support c-style enums, which could be good for eg: rendering.
+        //
+        // The downside is it's not always clear what the intent is, and how to merge static and instance data for particular
+        // languages. Eg: GeoJson could have { "type" : "..." } as static_data in an object here and merge that with another
+        // object in instance_data. So many questions though, like does static_data need to be the same type for each discriminant?
+        // If so, it would be an ArrayBranch with a fixed size.
+        let discriminant = read_ident(bytes, offset)?;
+        let value = read_next_root(bytes, offset, lens, limits)?.into();
+        Ok(DynRootBranch::Enum { discriminant, value })
     }
 
+    fn read_decimal<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut usize, limits: Limits) -> ReadResult<DynRootBranch<'a>> {
+        let scale = read_usize(bytes, offset)?.try_into().map_err(|_| ReadError::InvalidFormat)?;
+        let mantissa = Box::new(read_next_root(bytes, offset, lens, limits)?);
+        Ok(DynRootBranch::Decimal { mantissa, scale })
+    }
+
+    // Each arm results in a ReadResult rather than using ? so that in debug builds the arms
+    // don't each reserve stack space for a branch, which limits how deeply documents may be nested.
     use RootTypeId::*;
-    let branch = match id {
-        Void => DynRootBranch::Void,
-
-        Tuple2 => read_tuple(2, bytes, offset, lens)?,
-        Tuple3 => read_tuple(3, bytes, offset, lens)?,
-        Tuple4 => read_tuple(4, bytes, offset, lens)?,
-        Tuple5 => read_tuple(5, bytes, offset, lens)?,
-        Tuple6 => read_tuple(6, bytes, offset, lens)?,
-        Tuple7 => read_tuple(7, bytes, offset, lens)?,
-        Tuple8 => read_tuple(8, bytes, offset, lens)?,
-        TupleN => read_tuple(decode_prefix_varint(bytes, offset)? as usize + 9, bytes, offset, lens)?,
-
-        Array0 => DynRootBranch::Array0,
-        Array1 => DynRootBranch::Array1(Box::new(read_next_root(bytes, offset, lens)?)),
+    match id {
+        Void => Ok(DynRootBranch::Void),
+
+        Tuple1 => read_tuple(1, bytes, offset, lens, limits),
+        Tuple2 => read_tuple(2, bytes, offset, lens, limits),
+        Tuple3 => read_tuple(3, bytes, offset, lens, limits),
+        Tuple4 => read_tuple(4, bytes, offset, lens, limits),
+        Tuple5 => read_tuple(5, bytes, offset, lens, limits),
+        Tuple6 => read_tuple(6, bytes, offset, lens, limits),
+        Tuple7 => read_tuple(7, bytes, offset, lens, limits),
+        Tuple8 => read_tuple(8, bytes, offset, lens, limits),
+        TupleN => read_tuple(read_field_count(bytes, offset)?, bytes, offset, lens, limits),
+
+        Array0 => Ok(DynRootBranch::Array0),
+        Array1 => read_next_root(bytes, offset, lens, limits).map(|value| DynRootBranch::Array1(Box::new(value))),
         //Array2 => read_array(2, bytes, offset, lens)?,
         //Array3 => read_array(3, bytes, offset, lens)?,
         //Array4 => read_array(4, bytes, offset, lens)?,
         // TODO: usize - 2
-        ArrayN => read_array(read_usize(bytes, offset)?, bytes, offset, lens)?,
-        Map => {
-            let len = read_usize(bytes, offset)?;
-            match len {
-                0 => DynRootBranch::Map0,
-                1 => {
-                    let key = read_next_root(bytes, offset, lens)?.into();
-                    let value = read_next_root(bytes, offset, lens)?.into();
-                    DynRootBranch::Map1 { key, value }
-                }
-                _ => {
-                    let keys = read_next_array(bytes, offset, lens)?;
-                    let values = read_next_array(bytes, offset, lens)?;
-                    DynRootBranch::Map { len, keys, values }
-                }
-            }
-        }
+        ArrayN => read_array(limits.length(read_usize(bytes, offset)?)?, bytes, offset, lens, limits),
+        Map => read_map(limits.length(read_usize(bytes, offset)?)?, bytes, offset, lens, limits),
 
         // See also: fadaec14-35ad-4dc1-b6dc-6106ab811669
-        Obj0 => read_obj(0, bytes, offset, lens)?,
-        Obj1 => read_obj(1, bytes, offset, lens)?,
-        Obj2 => read_obj(2, bytes, offset, lens)?,
-        Obj3 => read_obj(3, bytes, offset, lens)?,
-        Obj4 => read_obj(4, bytes, offset, lens)?,
-        Obj5 => read_obj(5, bytes, offset, lens)?,
-        Obj6 => read_obj(6, bytes, offset, lens)?,
-        Obj7 => read_obj(7, bytes, offset, lens)?,
-        Obj8 => read_obj(8, bytes, offset, lens)?,
-        ObjN => read_obj(decode_prefix_varint(bytes, offset)? as usize + 9, bytes, offset, lens)?,
-
-        Enum => {
-            // TODO: Consider having the enum be:
-            //    Root: static_data: RootBranch, instance_data: RootBranch
-            //    Array: static_data: RootBranch, instance_data: ArrayBranch, discriminant: ArrayBranch(int)
-            // The interesting idea here is that it can be more powerful in that it supports complex value de-duplication
-            // Eg: like a primary key. One could represent more information (eg: Like a politician having a name and party
-            // when having a table for primaries and delegates). That might be more interesting as some kind of pointer type.
-            // It also would support c-style enums, which could be good for eg: rendering.
-            //
-            // The downside is it's not always clear what the intent is, and how to merge static and instance data for particular
-            // languages. Eg: GeoJson could have { "type" : "..." } as static_data in an object here and merge that with another
-            // object in instance_data. So many questions though, like does static_data need to be the same type for each discriminant?
-            // If so, it would be an ArrayBranch with a fixed size.
-            let discriminant = read_ident(bytes, offset)?;
-            let value = read_next_root(bytes, offset, lens)?.into();
-            DynRootBranch::Enum { discriminant, value }
-        }
+        Obj0 => read_obj(0, bytes, offset, lens, limits),
+        Obj1 => read_obj(1, bytes, offset, lens, limits),
+        Obj2 => read_obj(2, bytes, offset, lens, limits),
+        Obj3 => read_obj(3, bytes, offset, lens, limits),
+        Obj4 => read_obj(4, bytes, offset, lens, limits),
+        Obj5 => read_obj(5, bytes, offset, lens, limits),
+        Obj6 => read_obj(6, bytes, offset, lens, limits),
+        Obj7 => read_obj(7, bytes, offset, lens, limits),
+        Obj8 => read_obj(8, bytes, offset, lens, limits),
+        ObjN => read_obj(read_field_count(bytes, offset)?, bytes, offset, lens, limits),
 
+        Enum => read_enum(bytes, offset, lens, limits),
+        Decimal => read_decimal(bytes, offset, lens, limits),
+
+        _ => read_root_scalar(id, bytes, offset, limits),
+    }
+}
+
+/// Reads the branches which don't have children. These are separate from read_next_root
+/// to keep its stack frame small, since it is called once for each level of nesting.
+#[inline(never)]
+fn read_root_scalar<'a>(id: RootTypeId, bytes: &'a [u8], offset: &'_ mut usize, limits: Limits) -> ReadResult<DynRootBranch<'a>> {
+    fn read_str<'a>(len: usize, bytes: &'a [u8], offset: &'_ mut usize) -> ReadResult<DynRootBranch<'a>> {
+        let bytes = read_bytes(len, bytes, offset)?;
+        let s = std::str::from_utf8(bytes)?;
+        Ok(DynRootBranch::String(s))
+    }
+
+    use RootTypeId::*;
+    let branch = match id {
         True => DynRootBranch::Boolean(true),
         False => DynRootBranch::Boolean(false),
 
@@ -177,6 +206,8 @@ pub fn read_next_root<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut
         IntS24 => DynRootBranch::Integer(RootInteger::new(bytes, offset, 3, true)?),
         IntS16 => DynRootBranch::Integer(RootInteger::new(bytes, offset, 2, true)?),
         IntS8 => DynRootBranch::Integer(RootInteger::new(bytes, offset, 1, true)?),
+        IntU128 => DynRootBranch::Integer(RootInteger::U128(u128::from_le_bytes(read_16(bytes, offset)?))),
+        IntS128 => DynRootBranch::Integer(RootInteger::S128(i128::from_le_bytes(read_16(bytes, offset)?))),
 
         // Int Or Float
         Zero => DynRootBranch::Integer(RootInteger::U(0)),
@@ -192,21 +223,63 @@ pub fn read_next_root<'a>(bytes: &'a [u8], offset: &'_ mut usize, lens: &'_ mut
         Str1 => read_str(1, bytes, offset)?,
         Str2 => read_str(2, bytes, offset)?,
         Str3 => read_str(3, bytes, offset)?,
-        Str => read_str(decode_prefix_varint(bytes, offset)? as usize, bytes, offset)?,
+        Str => read_str(limits.string(read_usize(bytes, offset)?)?, bytes, offset)?,
+
+        Blob => {
+            let len = read_usize(bytes, offset)?;
+            DynRootBranch::Blob(BlobBytes::Raw(read_bytes(len, bytes, offset)?.into()))
+        }
+        BlobLz4 => {
+            let len = read_usize(bytes, offset)?;
+            let compressed_len = read_usize(bytes, offset)?;
+            let compressed = read_bytes(compressed_len, bytes, offset)?.into();
+            DynRootBranch::Blob(BlobBytes::Lz4 { len, compressed })
+        }
+        _ => unreachable!("Branches with children are read by read_next_root"),
     };
     Ok(branch)
 }
 
+impl<'a> DynRootBranch<'a> {
+    /// The kind of value in the document, for error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            DynRootBranch::Object { .. } => "Object",
+            DynRootBranch::Tuple { .. } => "Tuple",
+            DynRootBranch::Enum { .. } => "Enum",
+            DynRootBranch::Array0 | DynRootBranch::Array1(_) | DynRootBranch::Array { .. } => "Array",
+            DynRootBranch::Integer(_) => "Integer",
+            DynRootBranch::Boolean(_) => "Boolean",
+            DynRootBranch::Float(_) => "Float",
+            DynRootBranch::Void => "Void",
+            DynRootBranch::String(_) => "String",
+            DynRootBranch::Map0 | DynRootBranch::Map1 { .. } | DynRootBranch::Map { .. } => "Map",
+            DynRootBranch::Blob(_) => "Blob",
+            DynRootBranch::Decimal { .. } => "Decimal",
+        }
+    }
+}
+
 impl<'a> Default for DynRootBranch<'a> {
     fn default() -> Self {
         DynRootBranch::Void
     }
 }
 
+/// Integers which fit in 64 bits are always S or U,
+/// so that the 128 bit variants are only for larger magnitudes.
 #[derive(Debug)]
 pub enum RootInteger {
     S(i64),
     U(u64),
+    S128(i128),
+    U128(u128),
+}
+
+fn read_16(bytes: &[u8], offset: &mut usize) -> ReadResult<[u8; 16]> {
+    let mut result = [0; 16];
+    result.copy_from_slice(read_bytes(16, bytes, offset)?);
+    Ok(result)
 }
 
 impl_type_id!(RootTypeId, [
@@ -245,8 +318,24 @@ impl_type_id!(RootTypeId, [
     Str: 32,
     Enum: 33,
     Map: 34,
+    IntU128: 35,
+    IntS128: 36,
+    Blob: 37,
+    BlobLz4: 38,
+    Decimal: 39,
 ]);
 
+/// The byte to pad a truncated little-endian signed integer with
+/// so that the sign is extended into the missing high bytes.
+#[inline(always)]
+fn sign_fill(bytes: &[u8]) -> u8 {
+    if bytes[bytes.len() - 1] & 0x80 != 0 {
+        0xFF
+    } else {
+        0
+    }
+}
+
 impl RootInteger {
     #[inline(always)]
     pub fn new(bytes: &[u8], offset: &mut usize, len: usize, signed: bool) -> ReadResult<Self> {
@@ -261,7 +350,8 @@ impl RootInteger {
                 u32::from_le_bytes(b).into()
             }),
             (3, true) => Self::S({
-                let b = [bytes[0], bytes[1], bytes[2], 0];
+                let fill = sign_fill(bytes);
+                let b = [bytes[0], bytes[1], bytes[2], fill];
                 i32::from_le_bytes(b).into()
             }),
             (4, true) => Self::S(i32::from_le_bytes(bytes.try_into().unwrap()).into()),
@@ -271,7 +361,8 @@ impl RootInteger {
                 u64::from_le_bytes(b).into()
             }),
             (5, true) => Self::S({
-                let b = [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], 0, 0, 0];
+                let fill = sign_fill(bytes);
+                let b = [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], fill, fill, fill];
                 i64::from_le_bytes(b).into()
             }),
             (6, false) => Self::U({
@@ -279,7 +370,8 @@ impl RootInteger {
                 u64::from_le_bytes(b).into()
             }),
             (6, true) => Self::S({
-                let b = [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], 0, 0];
+                let fill = sign_fill(bytes);
+                let b = [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], fill, fill];
                 i64::from_le_bytes(b).into()
             }),
             (7, false) => Self::U({
@@ -287,7 +379,8 @@ impl RootInteger {
                 u64::from_le_bytes(b).into()
             }),
             (7, true) => Self::S({
-                let b = [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], 0];
+                let fill = sign_fill(bytes);
+                let b = [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], fill];
                 i64::from_le_bytes(b).into()
             }),
             (8, true) => Self::S(i64::from_le_bytes(bytes.try_into().unwrap()).into()),
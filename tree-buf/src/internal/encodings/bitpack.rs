@@ -0,0 +1,361 @@
+//! Integers written in blocks of 128 values, each packed into the fewest bits that fit the values of the block.
+//!
+//! Frame-of-reference first subtracts the minimum of each block, so that values which are clustered
+//! far from zero still pack into few bits. Patched frame-of-reference (PFOR) picks a narrower width that
+//! most values of the block fit in, and writes the high bits of the outliers separately.
+//!
+//! Each encoding starts with the number of values, followed by the blocks.
+
+use crate::internal::encodings::varint::*;
+use crate::prelude::*;
+
+pub const BLOCK_LEN: usize = 128;
+
+#[cfg(feature = "write")]
+fn bit_width(value: u64) -> u8 {
+    (64 - value.leading_zeros()) as u8
+}
+
+fn mask(width: u8) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Appends the low `width` bits of each value, least significant bits first.
+#[cfg(feature = "write")]
+pub fn pack(data: &[u64], width: u8, bytes: &mut Vec<u8>) {
+    profile!(&[u64], "pack");
+    let mut buffer = 0u128;
+    let mut buffered = 0u32;
+    for value in data {
+        buffer |= u128::from(value & mask(width)) << buffered;
+        buffered += u32::from(width);
+        while buffered >= 8 {
+            bytes.push(buffer as u8);
+            buffer >>= 8;
+            buffered -= 8;
+        }
+    }
+    if buffered != 0 {
+        bytes.push(buffer as u8);
+    }
+}
+
+#[cfg(feature = "read")]
+pub fn unpack(bytes: &[u8], offset: &mut usize, width: u8, count: usize, into: &mut Vec<u64>) -> ReadResult<()> {
+    profile!(&[u64], "unpack");
+    let width = u32::from(width);
+    let packed = read_bytes((count * width as usize + 7) / 8, bytes, offset)?;
+    let mut packed = packed.iter();
+    let mut buffer = 0u128;
+    let mut buffered = 0u32;
+    for _ in 0..count {
+        while buffered < width {
+            // The length of packed was checked above
+            buffer |= u128::from(*packed.next().unwrap()) << buffered;
+            buffered += 8;
+        }
+        into.push(buffer as u64 & mask(width as u8));
+        buffer >>= width;
+        buffered -= width;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "read")]
+fn read_width(bytes: &[u8], offset: &mut usize) -> ReadResult<u8> {
+    let width = read_bytes(1, bytes, offset)?[0];
+    if width > 64 {
+        return Err(ReadError::InvalidFormat);
+    }
+    Ok(width)
+}
+
+#[cfg(feature = "write")]
+fn encode_blocks(data: &[u64], bytes: &mut Vec<u8>, encode_block: impl Fn(&[u64], &mut Vec<u8>)) {
+    encode_prefix_varint(data.len() as u64, bytes);
+    for block in data.chunks(BLOCK_LEN) {
+        encode_block(block, bytes);
+    }
+}
+
+#[cfg(feature = "read")]
+fn decode_blocks(bytes: &[u8], limits: &Limits, decode_block: impl Fn(&[u8], &mut usize, usize, &mut Vec<u64>) -> ReadResult<()>) -> ReadResult<Vec<u64>> {
+    let mut offset = 0;
+    let count = read_usize(bytes, &mut offset)?;
+    // Every block takes at least a byte
+    if count / BLOCK_LEN > bytes.len() {
+        return Err(ReadError::InvalidFormat);
+    }
+    let mut values = try_vec_with_capacity(limits.collection::<u64>(count)?)?;
+    while values.len() < count {
+        let block_len = (count - values.len()).min(BLOCK_LEN);
+        decode_block(bytes, &mut offset, block_len, &mut values)?;
+    }
+    if offset != bytes.len() {
+        return Err(ReadError::InvalidFormat);
+    }
+    Ok(values)
+}
+
+#[cfg(feature = "write")]
+pub fn encode_bit_packed(data: &[u64], bytes: &mut Vec<u8>) {
+    profile!(&[u64], "encode_bit_packed");
+    encode_blocks(data, bytes, |block, bytes| {
+        let width = bit_width(block.iter().copied().max().unwrap_or_default());
+        bytes.push(width);
+        pack(block, width, bytes);
+    });
+}
+
+#[cfg(feature = "read")]
+pub fn decode_bit_packed(bytes: &[u8], limits: &Limits) -> ReadResult<Vec<u64>> {
+    profile!(&[u64], "decode_bit_packed");
+    decode_blocks(bytes, limits, |bytes, offset, count, into| {
+        let width = read_width(bytes, offset)?;
+        unpack(bytes, offset, width, count, into)
+    })
+}
+
+#[cfg(feature = "write")]
+fn offsets_from_min(block: &[u64], bytes: &mut Vec<u8>) -> Vec<u64> {
+    let min = block.iter().copied().min().unwrap_or_default();
+    encode_prefix_varint(min, bytes);
+    block.iter().map(|value| value - min).collect()
+}
+
+#[cfg(feature = "read")]
+fn add_min(bytes: &[u8], offset: &mut usize, into: &mut Vec<u64>, decode_offsets: impl FnOnce(&mut usize, &mut Vec<u64>) -> ReadResult<()>) -> ReadResult<()> {
+    let min = decode_prefix_varint(bytes, offset)?;
+    let start = into.len();
+    decode_offsets(offset, into)?;
+    for value in into[start..].iter_mut() {
+        *value = value.checked_add(min).ok_or(ReadError::InvalidFormat)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "write")]
+pub fn encode_frame_of_reference(data: &[u64], bytes: &mut Vec<u8>) {
+    profile!(&[u64], "encode_frame_of_reference");
+    encode_blocks(data, bytes, |block, bytes| {
+        let offsets = offsets_from_min(block, bytes);
+        let width = bit_width(offsets.iter().copied().max().unwrap_or_default());
+        bytes.push(width);
+        pack(&offsets, width, bytes);
+    });
+}
+
+#[cfg(feature = "read")]
+pub fn decode_frame_of_reference(bytes: &[u8], limits: &Limits) -> ReadResult<Vec<u64>> {
+    profile!(&[u64], "decode_frame_of_reference");
+    decode_blocks(bytes, limits, |bytes, offset, count, into| {
+        add_min(bytes, offset, into, |offset, into| {
+            let width = read_width(bytes, offset)?;
+            unpack(bytes, offset, width, count, into)
+        })
+    })
+}
+
+/// The size of a block of offsets packed to the width, with each value
+/// that does not fit as an exception of its index and high bits.
+#[cfg(feature = "write")]
+fn patched_size(offsets: &[u64], width: u8) -> usize {
+    let exceptions = offsets
+        .iter()
+        .filter(|offset| bit_width(**offset) > width)
+        .map(|offset| 1 + size_for_varint(offset >> width))
+        .sum::<usize>();
+    (offsets.len() * width as usize + 7) / 8 + exceptions
+}
+
+#[cfg(feature = "write")]
+pub fn encode_patched_frame_of_reference(data: &[u64], bytes: &mut Vec<u8>) {
+    profile!(&[u64], "encode_patched_frame_of_reference");
+    encode_blocks(data, bytes, |block, bytes| {
+        let offsets = offsets_from_min(block, bytes);
+        let max_width = bit_width(offsets.iter().copied().max().unwrap_or_default());
+        let width = (0..=max_width).min_by_key(|width| patched_size(&offsets, *width)).unwrap_or_default();
+        bytes.push(width);
+
+        let exceptions = offsets.iter().enumerate().filter(|(_, offset)| bit_width(**offset) > width).collect::<Vec<_>>();
+        encode_prefix_varint(exceptions.len() as u64, bytes);
+        pack(&offsets, width, bytes);
+        for (index, offset) in exceptions {
+            bytes.push(index as u8);
+            encode_prefix_varint(offset >> width, bytes);
+        }
+    });
+}
+
+#[cfg(feature = "read")]
+pub fn decode_patched_frame_of_reference(bytes: &[u8], limits: &Limits) -> ReadResult<Vec<u64>> {
+    profile!(&[u64], "decode_patched_frame_of_reference");
+    decode_blocks(bytes, limits, |bytes, offset, count, into| {
+        add_min(bytes, offset, into, |offset, into| {
+            let width = read_width(bytes, offset)?;
+            let exceptions = read_usize(bytes, offset)?;
+            if exceptions > count {
+                return Err(ReadError::InvalidFormat);
+            }
+            let start = into.len();
+            unpack(bytes, offset, width, count, into)?;
+            for _ in 0..exceptions {
+                let index = read_bytes(1, bytes, offset)?[0] as usize;
+                let high = decode_prefix_varint(bytes, offset)?;
+                // The high bits must not overflow, and only values wider than the block have exceptions
+                if index >= count || width == 64 || (high << width) >> width != high {
+                    return Err(ReadError::InvalidFormat);
+                }
+                into[start + index] |= high << width;
+            }
+            Ok(())
+        })
+    })
+}
+
+// Introduced in version 6 of the format
+#[cfg(feature = "write")]
+fn compress_blocks<T: Into<u64> + Copy, O: EncodeOptions>(data: &[T], stream: &mut WriterStream<'_, O>, encode: fn(&[u64], &mut Vec<u8>)) -> Result<(), ()> {
+    if stream.options.format_version() < 6 {
+        return Err(());
+    }
+    let data = data.iter().map(|v| (*v).into()).collect::<Vec<u64>>();
+    stream.write_with_len(|stream| encode(&data, stream.bytes));
+    Ok(())
+}
+
+#[cfg(feature = "write")]
+pub(crate) struct BitPackedCompressor;
+
+#[cfg(feature = "write")]
+impl BitPackedCompressor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "write")]
+impl<T: Into<u64> + Copy> Compressor<T> for BitPackedCompressor {
+    fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
+        profile!("compress");
+        compress_blocks(data, stream, encode_bit_packed)?;
+        Ok(ArrayTypeId::IntBitPacked)
+    }
+}
+
+#[cfg(feature = "write")]
+pub(crate) struct FrameOfReferenceCompressor;
+
+#[cfg(feature = "write")]
+impl FrameOfReferenceCompressor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "write")]
+impl<T: Into<u64> + Copy> Compressor<T> for FrameOfReferenceCompressor {
+    fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
+        profile!("compress");
+        compress_blocks(data, stream, encode_frame_of_reference)?;
+        Ok(ArrayTypeId::IntFrameOfReference)
+    }
+}
+
+#[cfg(feature = "write")]
+pub(crate) struct PatchedFrameOfReferenceCompressor;
+
+#[cfg(feature = "write")]
+impl PatchedFrameOfReferenceCompressor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "write")]
+impl<T: Into<u64> + Copy> Compressor<T> for PatchedFrameOfReferenceCompressor {
+    fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
+        profile!("compress");
+        compress_blocks(data, stream, encode_patched_frame_of_reference)?;
+        Ok(ArrayTypeId::IntPatchedFrameOfReference)
+    }
+}
+
+#[cfg(all(test, feature = "read", feature = "write"))]
+mod tests {
+    use super::*;
+
+    fn limits() -> Limits {
+        Limits::new(&DecodeOptionsDefault)
+    }
+
+    fn round_trip(data: &[u64], encode: fn(&[u64], &mut Vec<u8>), decode: fn(&[u8], &Limits) -> ReadResult<Vec<u64>>) -> usize {
+        let mut bytes = Vec::new();
+        encode(data, &mut bytes);
+        assert_eq!(decode(&bytes, &limits()).unwrap(), data);
+        bytes.len()
+    }
+
+    fn round_trip_all(data: &[u64]) {
+        round_trip(data, encode_bit_packed, decode_bit_packed);
+        round_trip(data, encode_frame_of_reference, decode_frame_of_reference);
+        round_trip(data, encode_patched_frame_of_reference, decode_patched_frame_of_reference);
+    }
+
+    #[test]
+    pub fn round_trips() {
+        round_trip_all(&[]);
+        round_trip_all(&[0]);
+        round_trip_all(&[u64::MAX, 0, 1, u64::MAX - 1]);
+        round_trip_all(&(0..1000).map(|i| i * 7919 % 1013).collect::<Vec<_>>());
+        round_trip_all(&(0..300).map(|i| if i % 50 == 0 { u64::MAX } else { i }).collect::<Vec<_>>());
+    }
+
+    #[test]
+    pub fn sizes() {
+        // Values far from zero
+        let data = (0..128).map(|i| 1_000_000 + i % 16).collect::<Vec<_>>();
+        assert_eq!(round_trip(&data, encode_bit_packed, decode_bit_packed), 2 + 1 + 320);
+        assert_eq!(round_trip(&data, encode_frame_of_reference, decode_frame_of_reference), 2 + 3 + 1 + 64);
+
+        // Outliers
+        let mut data = (0..128).map(|i| i % 4).collect::<Vec<_>>();
+        data[10] = 1 << 40;
+        assert_eq!(round_trip(&data, encode_frame_of_reference, decode_frame_of_reference), 2 + 1 + 1 + 656);
+        assert_eq!(
+            round_trip(&data, encode_patched_frame_of_reference, decode_patched_frame_of_reference),
+            2 + 1 + 1 + 1 + 32 + 1 + 6
+        );
+    }
+
+    fn with_count(count: u64, rest: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        encode_prefix_varint(count, &mut bytes);
+        bytes.extend_from_slice(rest);
+        bytes
+    }
+
+    #[test]
+    pub fn invalid() {
+        assert!(decode_bit_packed(&[], &limits()).is_err());
+        // Width too large
+        assert!(decode_bit_packed(&with_count(3, &[65]), &limits()).is_err());
+        // Too few bytes
+        assert!(decode_bit_packed(&with_count(5, &[8, 1]), &limits()).is_err());
+        // Trailing bytes
+        assert!(decode_bit_packed(&with_count(3, &[0, 0]), &limits()).is_err());
+        // More values than could possibly fit
+        assert!(decode_bit_packed(&with_count(1 << 40, &[]), &limits()).is_err());
+        // Exception out of the block
+        assert!(decode_patched_frame_of_reference(&with_count(1, &[1, 0, 3, 5, 3]), &limits()).is_err());
+        // Far more values than the limits allow, from blocks of width 0 which are one byte each
+        let bytes = with_count(1 << 28, &vec![0; (1 << 28) / BLOCK_LEN]);
+        assert_eq!(decode_bit_packed(&bytes, &limits()), Err(ReadError::LimitExceeded(Limit::Allocation)));
+        assert_eq!(decode_frame_of_reference(&bytes, &limits()), Err(ReadError::LimitExceeded(Limit::Allocation)));
+        assert_eq!(decode_patched_frame_of_reference(&bytes, &limits()), Err(ReadError::LimitExceeded(Limit::Allocation)));
+    }
+}
@@ -0,0 +1,69 @@
+//! Byte-stream-split, as in Parquet's BYTE_STREAM_SPLIT, writes the first byte of every value,
+//! then the second byte of every value, and so on. The bytes holding the sign and exponent of floats
+//! tend to repeat even when the mantissas are noisy, so the streams are then compressed with LZ4.
+//!
+//! The length of the split bytes is written first, followed by the compressed streams.
+
+use crate::internal::encodings::lz4;
+use crate::internal::encodings::varint::*;
+use crate::prelude::*;
+
+/// Splits the little-endian bytes of values which are each `width` bytes.
+#[cfg(feature = "write")]
+pub fn split(bytes: &[u8], width: usize) -> Vec<u8> {
+    profile!(&[u8], "split");
+    debug_assert_eq!(bytes.len() % width, 0);
+    let mut result = Vec::with_capacity(bytes.len());
+    for stream in 0..width {
+        result.extend(bytes.iter().skip(stream).step_by(width));
+    }
+    result
+}
+
+#[cfg(feature = "read")]
+pub fn join(bytes: &[u8], width: usize) -> ReadResult<Vec<u8>> {
+    profile!(&[u8], "join");
+    if bytes.len() % width != 0 {
+        return Err(ReadError::InvalidFormat);
+    }
+    let count = bytes.len() / width;
+    let mut result = vec![0; bytes.len()];
+    for (stream, bytes) in bytes.chunks_exact(count.max(1)).enumerate() {
+        for (i, byte) in bytes.iter().enumerate() {
+            result[i * width + stream] = *byte;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(feature = "write")]
+pub fn compress(bytes: &[u8], width: usize, into: &mut Vec<u8>) {
+    profile!(&[u8], "compress");
+    let split = split(bytes, width);
+    encode_prefix_varint(split.len() as u64, into);
+    into.extend_from_slice(&lz4::compress(&split));
+}
+
+#[cfg(feature = "read")]
+pub fn decompress(bytes: &[u8], width: usize, limits: &Limits) -> ReadResult<Vec<u8>> {
+    profile!(&[u8], "decompress");
+    let mut offset = 0;
+    let len = limits.allocation(read_usize(bytes, &mut offset)?)?;
+    let split = lz4::decompress(&bytes[offset..], len)?;
+    join(&split, width)
+}
+
+#[cfg(all(test, feature = "read", feature = "write"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn split_and_join() {
+        let bytes = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        assert_eq!(split(&bytes, 4), vec![1, 5, 9, 2, 6, 10, 3, 7, 11, 4, 8, 12]);
+        assert_eq!(join(&split(&bytes, 4), 4).unwrap(), bytes);
+        assert_eq!(join(&split(&bytes, 3), 3).unwrap(), bytes);
+        assert_eq!(join(&[], 8).unwrap(), Vec::<u8>::new());
+        assert!(join(&bytes[..5], 4).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 use crate::prelude::*;
+#[cfg(feature = "write")]
+use std::hash::Hash;
 
 #[cfg(feature = "write")]
 pub(crate) fn compress<T: PartialEq + Default, O: EncodeOptions>(data: &[T], stream: &mut WriterStream<'_, O>, compressors: &impl CompressorSet<T>) -> ArrayTypeId {
@@ -75,6 +77,14 @@ pub(crate) trait Compressor<T> {
     fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()>;
 }
 
+/// Values are only the same if they are the same bit-for-bit. This is what RLE and Dictionary
+/// compare so that values round trip exactly. Eg: NaN is the same as NaN, but 0.0 and -0.0 differ.
+#[cfg(feature = "write")]
+pub(crate) trait BitEq {
+    type Bits: Hash + Eq;
+    fn bits(&self) -> Self::Bits;
+}
+
 
 pub (crate) trait CompressorSet<T> {
     fn len(&self) -> usize;
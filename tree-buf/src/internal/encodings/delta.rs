@@ -1,33 +1,132 @@
+//! Stores the differences between consecutive integers, which are small for eg: sorted ids.
+//! Applying this twice (delta-of-delta) turns evenly spaced values like timestamps into runs of zeros.
+
 use crate::prelude::*;
-use std::ops::{Add, Sub};
+#[cfg(feature = "write")]
+use std::cell::RefCell;
+#[cfg(feature = "write")]
+use std::thread_local;
+use zigzag::ZigZag;
+
+// See also IN_RLE_ENCODE
+#[cfg(feature = "write")]
+thread_local! {
+    static IN_DELTA_ENCODE: RefCell<bool> = const { RefCell::new(false) };
+}
+
+#[cfg(feature = "write")]
+fn get_in_delta() -> bool {
+    IN_DELTA_ENCODE.with(|v| *v.borrow())
+}
+#[cfg(feature = "write")]
+fn set_in_delta(value: bool) {
+    IN_DELTA_ENCODE.with(|v| *v.borrow_mut() = value);
+}
+
+/// The differences wrap, so that any value may follow any other. They are zigzag
+/// encoded, so that a small decrease is as small as a small increase.
+pub fn delta_encode(data: &[u64]) -> Vec<u64> {
+    profile!(&[u64], "delta_encode");
+    let mut previous = 0u64;
+    data.iter()
+        .map(|&value| {
+            let delta = value.wrapping_sub(previous) as i64;
+            previous = value;
+            ZigZag::encode(delta)
+        })
+        .collect()
+}
 
-// FIXME: This may not be what is needed. Zigzag may be.
-pub fn delta_encode_in_place<T: Sub<Output = T> + Copy>(data: &mut [T]) {
-    profile!(T, "delta_encode_in_place");
-    if data.len() == 0 {
-        return;
+pub fn delta_decode_in_place(data: &mut [u64]) {
+    profile!(&[u64], "delta_decode_in_place");
+    let mut previous = 0u64;
+    for value in data.iter_mut() {
+        previous = previous.wrapping_add(<i64 as ZigZag>::decode(*value) as u64);
+        *value = previous;
     }
-    let mut current = data[0];
-    for i in 1..data.len() {
-        let next = data[i];
-        data[i] = next - current;
-        current = next;
+}
+
+/// Writes the deltas of the data, nested in a Delta branch.
+#[cfg(feature = "write")]
+pub(crate) struct Delta;
+
+/// Writes the deltas of the deltas of the data, nested in a DeltaOfDelta branch.
+#[cfg(feature = "write")]
+pub(crate) struct DeltaOfDelta;
+
+#[cfg(feature = "write")]
+impl Delta {
+    pub fn new() -> Self {
+        Self
     }
 }
 
-pub fn delta_decode_in_place<T: Add<Output = T> + Copy>(data: &mut [T]) {
-    profile!(T, "delta_decode_in_place");
-    for i in 1..data.len() {
-        data[i] = data[i] + data[i - 1];
+#[cfg(feature = "write")]
+impl DeltaOfDelta {
+    pub fn new() -> Self {
+        Self
     }
 }
 
-#[cfg(tests)]
+#[cfg(feature = "write")]
+fn compress_deltas<T: Into<u64> + Copy, O: EncodeOptions>(data: &[T], order: usize, stream: &mut WriterStream<'_, O>) -> Result<(), ()> {
+    // Introduced in version 5 of the format
+    if stream.options.format_version() < 5 {
+        return Err(());
+    }
+    // Nesting is what delta-of-delta is for, and the deltas
+    // of fewer than 2 items are never smaller than the items.
+    if get_in_delta() || data.len() < 2 {
+        return Err(());
+    }
+
+    let mut deltas = data.iter().map(|v| (*v).into()).collect::<Vec<u64>>();
+    for _ in 0..order {
+        deltas = delta_encode(&deltas);
+    }
+
+    write_usize(deltas.len(), stream);
+    set_in_delta(true);
+    stream.write_with_id(|stream| write_uints(&deltas, stream));
+    set_in_delta(false);
+    Ok(())
+}
+
+#[cfg(feature = "write")]
+impl<T: Into<u64> + Copy> Compressor<T> for Delta {
+    fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
+        profile!(&[T], "Delta::compress");
+        compress_deltas(data, 1, stream)?;
+        Ok(ArrayTypeId::Delta)
+    }
+}
+
+#[cfg(feature = "write")]
+impl<T: Into<u64> + Copy> Compressor<T> for DeltaOfDelta {
+    fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
+        profile!(&[T], "DeltaOfDelta::compress");
+        compress_deltas(data, 2, stream)?;
+        Ok(ArrayTypeId::DeltaOfDelta)
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+
     #[test]
     pub fn round_trip() {
-        let tests = vec![vec![], vec![10], vec![0, 1]];
-        for test in tests {}
+        let tests = vec![vec![], vec![10], vec![0, 1], vec![5, 3, 9, 9, 1], vec![u64::MAX, 0, u64::MAX, 1, u64::MAX - 1]];
+        for test in tests {
+            let mut deltas = delta_encode(&test);
+            delta_decode_in_place(&mut deltas);
+            assert_eq!(test, deltas);
+        }
+    }
+
+    #[test]
+    pub fn small_steps() {
+        assert_eq!(delta_encode(&[10, 11, 12, 11]), vec![20, 2, 2, 1]);
+        assert_eq!(delta_encode(&delta_encode(&[100, 200, 300, 400])), vec![400, 0, 0, 0]);
     }
 }
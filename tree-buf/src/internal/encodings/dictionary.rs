@@ -1,7 +1,6 @@
 use crate::prelude::*;
 use std::collections::HashMap;
 use std::convert::TryInto as _;
-use std::hash::Hash;
 use std::vec::IntoIter;
 
 // TODO: usize
@@ -12,43 +11,35 @@ pub struct DictionaryIterator<T> {
     // to remove a 0 from the end of indexes. If both happen, this could be a problem.
     // See also 522d2f4f-c5f7-478c-8d94-e7457ae45b29
     indexes: IntoIter<u64>,
-    values: IntoIter<T>,
-    cache: HashMap<u64, T>,
+    values: Vec<T>,
 }
 
 impl<T: Clone + Default> Iterator for DictionaryIterator<T> {
     type Item = T;
     fn next(&mut self) -> Option<T> {
-        match self.indexes.next() {
-            None => None,
-            Some(index) => {
-                while self.cache.len() <= index as usize {
-                    // Have to use unwrap_or_default() here, because we used the compress fn
-                    let next = self.values.next().unwrap_or_default();
-                    self.cache.insert(self.cache.len() as u64, next);
-                }
-                Some(self.cache.get(&index).unwrap().clone())
-            }
-        }
+        // Have to use unwrap_or_default() here, because we used the compress fn
+        self.indexes.next().map(|index| self.values.get(index as usize).cloned().unwrap_or_default())
     }
 }
 
 impl<T: Send + Clone> DictionaryIterator<T> {
-    pub fn new(
-        indexes: Box<DynArrayBranch<'_>>,
-        values: Box<DynArrayBranch<'_>>,
+    pub fn new<'a>(
+        indexes: Box<DynArrayBranch<'a>>,
+        values: Box<DynArrayBranch<'a>>,
         options: &impl DecodeOptions,
-        f: impl Send + FnOnce(DynArrayBranch<'_>) -> ReadResult<IntoIter<T>>,
+        f: impl Send + FnOnce(DynArrayBranch<'a>) -> ReadResult<IntoIter<T>>,
     ) -> ReadResult<Self> {
         let (indexes, values) = parallel(|| <u64 as Readable>::ReaderArray::new(*indexes, options), || f(*values), options);
         let indexes = indexes?;
-        let values = values?;
+        let values: Vec<T> = values?.collect();
+
+        // The values are unique, so at most one default value may have been removed from the end.
+        // Any index past that is from a corrupt document.
+        if indexes.as_slice().iter().any(|index| *index > values.len() as u64) {
+            return Err(ReadError::InvalidFormat);
+        }
 
-        Ok(Self {
-            indexes,
-            values,
-            cache: HashMap::new(),
-        })
+        Ok(Self { indexes, values })
     }
 }
 
@@ -63,7 +54,7 @@ impl<S> Dictionary<S> {
     }
 }
 
-impl<T: PartialEq + Copy + Default + std::fmt::Debug + Hash + Eq, S: CompressorSet<T>> Compressor<T> for Dictionary<S> {
+impl<T: PartialEq + BitEq + Copy + Default + std::fmt::Debug, S: CompressorSet<T>> Compressor<T> for Dictionary<S> {
     fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
         // Prevent panic on indexing first item.
         profile!("compress");
@@ -78,11 +69,11 @@ impl<T: PartialEq + Copy + Default + std::fmt::Debug + Hash + Eq, S: CompressorS
         let mut lookup = HashMap::new();
 
         for value in data.iter() {
-            let index = if let Some(i) = lookup.get(value) {
+            let index = if let Some(i) = lookup.get(&value.bits()) {
                 *i
             } else {
                 let i = lookup.len();
-                lookup.insert(value, i);
+                lookup.insert(value.bits(), i);
                 values.push(*value);
                 i
             };
@@ -1,16 +1,99 @@
 use crate::prelude::*;
 use num_traits::AsPrimitive;
 
-// TODO: Remove warning
-#[allow(dead_code)]
-pub fn decompress<T: 'static + Copy>(_bytes: &[u8]) -> ReadResult<Vec<T>>
+/// Reads bits starting with the most significant bit of each word.
+struct BitReader {
+    words: Vec<u64>,
+    position: usize,
+    len: usize,
+}
+
+impl BitReader {
+    fn remaining(&self) -> usize {
+        self.len - self.position
+    }
+
+    fn read(&mut self, count: u8) -> ReadResult<u64> {
+        let count = count as usize;
+        if count == 0 {
+            return Ok(0);
+        }
+        if count > self.remaining() {
+            return Err(ReadError::InvalidFormat);
+        }
+        let index = self.position / 64;
+        let bit = self.position % 64;
+        let available = 64 - bit;
+        let word = self.words[index] & (u64::MAX >> bit);
+        self.position += count;
+        if count <= available {
+            Ok(word >> (available - count))
+        } else {
+            let low = count - available;
+            Ok((word << low) | (self.words[index + 1] >> (64 - low)))
+        }
+    }
+}
+
+pub fn decompress<T: 'static + Copy>(bytes: &[u8]) -> ReadResult<Vec<T>>
 where
     f64: AsPrimitive<T>,
 {
-    todo!()
+    // FIXME: Should do schema mismatch for f32 -> f64
+    let (bits_in_last, bytes) = bytes.split_last().ok_or(ReadError::InvalidFormat)?;
+    if *bits_in_last > 64 {
+        return Err(ReadError::InvalidFormat);
+    }
+
+    // The last word is truncated to the bytes that have bits in them.
+    let (full, last) = bytes.split_at(bytes.len() - (bytes.len() % 8));
+    let mut words: Vec<u64> = full
+        .chunks_exact(8)
+        .map(|chunk| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            u64::from_le_bytes(word)
+        })
+        .collect();
+    let mut last_word = [0u8; 8];
+    last_word[8 - last.len()..].copy_from_slice(last);
+    words.push(u64::from_le_bytes(last_word));
+
+    let len = (words.len() - 1) * 64 + *bits_in_last as usize;
+    let mut reader = BitReader { words, position: 0, len };
+
+    let mut values = Vec::new();
+    if reader.remaining() < 64 {
+        return Ok(values);
+    }
+    let mut value = reader.read(64)?;
+    let mut xor = value;
+    values.push(f64::from_bits(value).as_());
+
+    // See also compress
+    while reader.remaining() != 0 {
+        if reader.read(1)? == 1 {
+            xor = if reader.read(1)? == 0 {
+                // Reuse the window of the previous xor
+                let prev_lz = xor.leading_zeros() as u64;
+                let prev_tz = if prev_lz == 64 { 0 } else { xor.trailing_zeros() as u64 };
+                let meaningful_bit_count = 64 - prev_tz - prev_lz;
+                reader.read(meaningful_bit_count as u8)? << prev_tz
+            } else {
+                let lz = reader.read(5)?;
+                let meaningful_bit_count = reader.read(6)? + 1;
+                let tz = 64u64.checked_sub(meaningful_bit_count + lz).ok_or(ReadError::InvalidFormat)?;
+                reader.read(meaningful_bit_count as u8)? << tz
+            };
+            value ^= xor;
+        }
+        values.push(f64::from_bits(value).as_());
+    }
+
+    Ok(values)
 }
 
-pub fn compress(data: impl Iterator<Item = f64>, bytes: &mut Vec<u8>) -> Result<ArrayTypeId, ()> {
+pub fn compress(data: impl Iterator<Item = f64>, bytes: &mut Vec<u8>) -> ArrayTypeId {
     // FIXME: Verify current platform is little endian
     let mut data = data.map(f64::to_bits);
 
@@ -29,7 +112,11 @@ pub fn compress(data: impl Iterator<Item = f64>, bytes: &mut Vec<u8>) -> Result<
 
     let mut buffer = match data.next() {
         Some(first) => first,
-        None => return Err(()),
+        None => {
+            // Only the count of bits in the last word, which is 0
+            bytes.push(0);
+            return ArrayTypeId::DoubleGorilla;
+        }
     };
 
     let mut previous = buffer;
@@ -79,8 +166,8 @@ pub fn compress(data: impl Iterator<Item = f64>, bytes: &mut Vec<u8>) -> Result<
         byte_count += 1;
     }
     let last = &(&buffer.to_le_bytes())[(8 - byte_count) as usize..];
-    bytes.extend_from_slice(&last);
+    bytes.extend_from_slice(last);
     bytes.push(remaining);
 
-    Ok(ArrayTypeId::DoubleGorilla)
+    ArrayTypeId::DoubleGorilla
 }
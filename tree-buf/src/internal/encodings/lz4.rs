@@ -0,0 +1,22 @@
+//! General purpose compression for opaque bytes, which have no structure
+//! that the other encodings could take advantage of.
+
+use crate::prelude::*;
+
+#[cfg(feature = "write")]
+pub fn compress(bytes: &[u8]) -> Vec<u8> {
+    lz4_flex::block::compress(bytes)
+}
+
+/// The length of the decompressed bytes must be known ahead of time, and
+/// is checked against what was actually decompressed.
+#[cfg(feature = "read")]
+pub fn decompress(bytes: &[u8], len: usize) -> ReadResult<Vec<u8>> {
+    let mut result = try_vec_with_capacity(len)?;
+    result.resize(len, 0);
+    let written = lz4_flex::block::decompress_into(bytes, &mut result).map_err(|_| ReadError::InvalidFormat)?;
+    if written != len {
+        return Err(ReadError::InvalidFormat);
+    }
+    Ok(result)
+}
@@ -1,21 +1,29 @@
+pub mod bitpack;
+pub mod byte_stream_split;
 mod compress;
 pub mod delta;
 mod dictionary;
+pub mod lz4;
 pub mod packed_bool;
 pub mod rle;
 pub mod rle_bool;
+pub mod simple_8b;
 pub mod varint;
 
 use crate::prelude::*;
 pub(crate) use compress::*;
+#[cfg(feature = "write")]
+pub(crate) use bitpack::{BitPackedCompressor, FrameOfReferenceCompressor, PatchedFrameOfReferenceCompressor};
+#[cfg(feature = "write")]
+pub(crate) use delta::{Delta, DeltaOfDelta};
 pub(crate) use dictionary::*;
 pub(crate) use rle::*;
+#[cfg(feature = "write")]
+pub(crate) use simple_8b::Simple8bCompressor;
 
 mod gorilla_new;
-mod gorilla_old;
 pub mod gorilla {
-    pub use super::gorilla_new::compress;
-    pub use super::gorilla_old::decompress;
+    pub use super::gorilla_new::{compress, decompress};
 }
 
 //pub mod zfp;
@@ -25,7 +33,7 @@ pub(crate) struct Utf8Compressor;
 
 #[cfg(feature = "read")]
 /// Reads all items from some byte aligned encoding
-pub fn read_all<T>(bytes: &[u8], f: impl Fn(&[u8], &mut usize) -> ReadResult<T>) -> ReadResult<Vec<T>> {
+pub fn read_all<'a, T>(bytes: &'a [u8], f: impl Fn(&'a [u8], &mut usize) -> ReadResult<T>) -> ReadResult<Vec<T>> {
     profile!(T, "read_all");
     let mut offset = 0;
     let mut result = Vec::new();
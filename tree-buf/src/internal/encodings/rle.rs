@@ -50,15 +50,16 @@ impl<T: Clone> Iterator for RleIterator<T> {
 }
 
 impl<T: Send + Clone> RleIterator<T> {
-    pub fn new(
-        runs: Box<DynArrayBranch<'_>>,
-        values: Box<DynArrayBranch<'_>>,
+    pub fn new<'a>(
+        runs: Box<DynArrayBranch<'a>>,
+        values: Box<DynArrayBranch<'a>>,
         options: &impl DecodeOptions,
-        f: impl Send + FnOnce(DynArrayBranch<'_>) -> ReadResult<IntoIter<T>>,
+        f: impl Send + FnOnce(DynArrayBranch<'a>) -> ReadResult<IntoIter<T>>,
     ) -> ReadResult<Self> {
         let (runs, values) = parallel(|| <u64 as Readable>::ReaderArray::new(*runs, options), || f(*values), options);
         let runs = runs?;
         let values = values?;
+        Limits::new(options).runs::<T>(runs.as_slice())?;
 
         Ok(Self {
             current_run: None,
@@ -82,7 +83,7 @@ impl<S> RLE<S> {
 
 
 
-impl<T: PartialEq + Copy + Default + std::fmt::Debug, S: CompressorSet<T>> Compressor<T> for RLE<S> {
+impl<T: PartialEq + BitEq + Copy + Default + std::fmt::Debug, S: CompressorSet<T>> Compressor<T> for RLE<S> {
     fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
         // Nesting creates performance problems
         if get_in_rle() {
@@ -102,7 +103,7 @@ impl<T: PartialEq + Copy + Default + std::fmt::Debug, S: CompressorSet<T>> Compr
         let mut current_value = data[0];
         let mut values = vec![];
         for item in data[1..].iter() {
-            if current_value == *item {
+            if current_value.bits() == item.bits() {
                 current_run += 1;
             } else {
                 runs.push(current_run);
@@ -0,0 +1,164 @@
+//! Simple-8b packs as many integers as fit into each 64 bit word, all of the same width.
+//! The low 4 bits of each word select how many integers there are and how wide they are,
+//! and the remaining 60 bits hold the integers, least significant first.
+//!
+//! Like Simple16 for u32, this can't represent every value. Integers must fit in 60 bits.
+//! The number of integers is written first, because the last word may be padded with zeros.
+
+use crate::internal::encodings::varint::*;
+use crate::prelude::*;
+
+/// The count and width of the integers in a word, indexed by selector.
+const SELECTORS: [(usize, usize); 16] = [
+    (240, 0),
+    (120, 0),
+    (60, 1),
+    (30, 2),
+    (20, 3),
+    (15, 4),
+    (12, 5),
+    (10, 6),
+    (8, 7),
+    (7, 8),
+    (6, 10),
+    (5, 12),
+    (4, 15),
+    (3, 20),
+    (2, 30),
+    (1, 60),
+];
+
+#[cfg(feature = "write")]
+pub(crate) fn compress(data: &[u64], bytes: &mut Vec<u8>) -> Result<(), ()> {
+    profile!(&[u64], "compress");
+    encode_prefix_varint(data.len() as u64, bytes);
+    let mut remaining = data;
+    while !remaining.is_empty() {
+        let (selector, (count, width)) = SELECTORS
+            .iter()
+            .enumerate()
+            .find(|(_, (count, width))| remaining.iter().take(*count).all(|value| value >> width == 0))
+            .ok_or(())?;
+        let count = (*count).min(remaining.len());
+        let mut word = selector as u64;
+        for (i, value) in remaining[..count].iter().enumerate() {
+            word |= value << (4 + i * width);
+        }
+        bytes.extend_from_slice(&word.to_le_bytes());
+        remaining = &remaining[count..];
+    }
+    Ok(())
+}
+
+#[cfg(feature = "read")]
+pub(crate) fn decompress(bytes: &[u8], limits: &Limits) -> ReadResult<Vec<u64>> {
+    profile!(&[u64], "decompress");
+    let mut offset = 0;
+    let count = read_usize(bytes, &mut offset)?;
+    let words = (bytes.len() - offset) / 8;
+    if (bytes.len() - offset) % 8 != 0 || count / SELECTORS[0].0 > words {
+        return Err(ReadError::InvalidFormat);
+    }
+    let mut values = try_vec_with_capacity(limits.collection::<u64>(count)?)?;
+    for _ in 0..words {
+        // Every word must have at least one of the integers
+        if values.len() >= count {
+            return Err(ReadError::InvalidFormat);
+        }
+        let word = read_bytes(8, bytes, &mut offset)?;
+        let word = u64::from_le_bytes([word[0], word[1], word[2], word[3], word[4], word[5], word[6], word[7]]);
+        let (len, width) = SELECTORS[(word & 0xF) as usize];
+        let mask = (1 << width) - 1;
+        for i in 0..len {
+            values.push((word >> (4 + i * width)) & mask);
+        }
+    }
+    if values.len() < count {
+        return Err(ReadError::InvalidFormat);
+    }
+    values.truncate(count);
+    Ok(values)
+}
+
+#[cfg(feature = "write")]
+pub(crate) struct Simple8bCompressor;
+
+#[cfg(feature = "write")]
+impl Simple8bCompressor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "write")]
+impl<T: Into<u64> + Copy> Compressor<T> for Simple8bCompressor {
+    fn compress<O: EncodeOptions>(&self, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
+        profile!("compress");
+        // Introduced in version 7 of the format
+        if stream.options.format_version() < 7 {
+            return Err(());
+        }
+        let data = data.iter().map(|v| (*v).into()).collect::<Vec<u64>>();
+        stream.write_with_len(|stream| compress(&data, stream.bytes))?;
+        Ok(ArrayTypeId::IntSimple8b)
+    }
+}
+
+#[cfg(all(test, feature = "read", feature = "write"))]
+mod tests {
+    use super::*;
+
+    fn limits() -> Limits {
+        Limits::new(&DecodeOptionsDefault)
+    }
+
+    fn round_trip(data: &[u64]) -> usize {
+        let mut bytes = Vec::new();
+        compress(data, &mut bytes).unwrap();
+        assert_eq!(decompress(&bytes, &limits()).unwrap(), data);
+        bytes.len()
+    }
+
+    #[test]
+    pub fn round_trips() {
+        assert_eq!(round_trip(&[]), 1);
+        assert_eq!(round_trip(&[0; 3]), 1 + 8);
+        assert_eq!(round_trip(&[0; 241]), 2 + 16);
+        assert_eq!(round_trip(&[1; 60]), 1 + 8);
+        assert_eq!(round_trip(&[(1 << 60) - 1, 0, 1]), 1 + 16);
+        // Each width followed by a wider value
+        let data = SELECTORS.iter().flat_map(|(_, width)| vec![(1u64 << width) - 1, 1 << width]).collect::<Vec<_>>();
+        round_trip(&data[..data.len() - 1]);
+    }
+
+    #[test]
+    pub fn too_wide() {
+        assert!(compress(&[1, 1 << 60], &mut Vec::new()).is_err());
+        assert!(compress(&[u64::MAX], &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    pub fn invalid() {
+        let mut bytes = Vec::new();
+        compress(&[1, 2, 3], &mut bytes).unwrap();
+        // Not a whole word
+        assert!(decompress(&bytes[..bytes.len() - 1], &limits()).is_err());
+        // Fewer integers than the count
+        bytes[0] = 0xFF;
+        assert!(decompress(&bytes, &limits()).is_err());
+        // An extra word
+        let mut bytes = Vec::new();
+        compress(&[1; 70], &mut bytes).unwrap();
+        bytes.extend_from_slice(&[0; 8]);
+        assert!(decompress(&bytes, &limits()).is_err());
+    }
+
+    #[test]
+    pub fn too_many() {
+        // Each word of zeros claims 240 integers
+        let mut bytes = Vec::new();
+        encode_prefix_varint(1 << 28, &mut bytes);
+        bytes.resize(bytes.len() + ((1 << 28) / 240 + 1) * 8, 0);
+        assert_eq!(decompress(&bytes, &limits()), Err(ReadError::LimitExceeded(Limit::Allocation)));
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "read")]
+use crate::internal::limits::Limit;
 use std::fmt::{Debug, Display, Formatter};
 
 #[cfg(feature = "read")]
@@ -10,17 +12,217 @@ pub enum ReadError {
     // so it's not useful information. Removing the variants makes it so that at
     // least for now we can avoid boxing.
     InvalidFormat,
+    /// The document was written with a version of the format this version of tree-buf does not understand.
+    UnsupportedFormatVersion(u8),
+    /// The document exceeded one of the limits set in the `DecodeOptions`.
+    LimitExceeded(Limit),
+    /// One of the above errors, along with where in the document it happened.
+    /// Collecting the context requires boxing, so this is only produced
+    /// when the "diagnostics" feature is enabled. See also `ReadError::kind`.
+    #[cfg(feature = "diagnostics")]
+    Context(Box<ErrorContext>),
 }
 
 use coercible_errors::coercible_errors;
 coercible_errors!(ReadError);
 
+/// Where in the document a `ReadError` happened.
+#[cfg(all(feature = "read", feature = "diagnostics"))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ErrorContext {
+    /// The error which happened. This is never `ReadError::Context`.
+    pub error: ReadError,
+    /// The path from the root of the document to the value which failed to read,
+    /// with the outermost segment first.
+    pub path: Vec<PathSegment>,
+    /// The Rust type that was being read
+    pub expected: Option<&'static str>,
+    /// The kind of value that was found in the document
+    pub found: Option<&'static str>,
+    /// How far into the document parsing got before a format error
+    pub offset: Option<usize>,
+}
+
+#[cfg(all(feature = "read", feature = "diagnostics"))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PathSegment {
+    /// A field of an object, or a variant of an enum
+    Field(&'static str),
+    /// A field of a tuple
+    Index(usize),
+    /// The items of an array, or the keys and values of a map
+    Element,
+}
+
+#[cfg(all(feature = "read", feature = "diagnostics"))]
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            PathSegment::Field(name) => f.write_str(name),
+            PathSegment::Index(index) => write!(f, "{}", index),
+            PathSegment::Element => f.write_str("[]"),
+        }
+    }
+}
+
+#[cfg(all(feature = "read", feature = "diagnostics"))]
+impl ErrorContext {
+    /// The path formatted like `features[].geometry.coordinates`
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for segment in self.path.iter() {
+            if !path.is_empty() && *segment != PathSegment::Element {
+                path.push('.');
+            }
+            path.push_str(&segment.to_string());
+        }
+        path
+    }
+}
+
+#[cfg(feature = "read")]
+impl ReadError {
+    /// The document had a different kind of value (`found`) than the type `T` being read.
+    #[inline(always)]
+    pub fn schema_mismatch<T: ?Sized>(found: &'static str) -> Self {
+        #[cfg(feature = "diagnostics")]
+        {
+            ReadError::SchemaMismatch.with_context(|context| {
+                context.expected = Some(std::any::type_name::<T>());
+                context.found = Some(found);
+            })
+        }
+        #[cfg(not(feature = "diagnostics"))]
+        {
+            let _ = found;
+            ReadError::SchemaMismatch
+        }
+    }
+
+    /// The error, without any context
+    pub fn kind(&self) -> &ReadError {
+        match self {
+            #[cfg(feature = "diagnostics")]
+            ReadError::Context(context) => &context.error,
+            _ => self,
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            ReadError::Context(context) => Some(context),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn with_context(self, f: impl FnOnce(&mut ErrorContext)) -> Self {
+        let mut context = match self {
+            ReadError::Context(context) => context,
+            error => Box::new(ErrorContext {
+                error,
+                path: Vec::new(),
+                expected: None,
+                found: None,
+                offset: None,
+            }),
+        };
+        f(&mut context);
+        ReadError::Context(context)
+    }
+
+    /// Records the byte offset of a format error, unless one is already known.
+    #[inline(always)]
+    pub fn at_offset(self, offset: usize) -> Self {
+        #[cfg(feature = "diagnostics")]
+        {
+            if *self.kind() == ReadError::SchemaMismatch {
+                return self;
+            }
+            self.with_context(|context| {
+                context.offset.get_or_insert(offset);
+            })
+        }
+        #[cfg(not(feature = "diagnostics"))]
+        {
+            let _ = offset;
+            self
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn in_segment(self, segment: PathSegment) -> Self {
+        self.with_context(|context| context.path.insert(0, segment))
+    }
+
+    /// Prefixes the path of the error with the field of an object or the variant of an enum.
+    #[inline(always)]
+    pub fn in_field(self, name: &'static str) -> Self {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.in_segment(PathSegment::Field(name))
+        }
+        #[cfg(not(feature = "diagnostics"))]
+        {
+            let _ = name;
+            self
+        }
+    }
+
+    /// Prefixes the path of the error with the field of a tuple.
+    #[inline(always)]
+    pub fn in_index(self, index: usize) -> Self {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.in_segment(PathSegment::Index(index))
+        }
+        #[cfg(not(feature = "diagnostics"))]
+        {
+            let _ = index;
+            self
+        }
+    }
+
+    /// Prefixes the path of the error with the items of an array or map.
+    #[inline(always)]
+    pub fn in_element(self) -> Self {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.in_segment(PathSegment::Element)
+        }
+        #[cfg(not(feature = "diagnostics"))]
+        {
+            self
+        }
+    }
+}
+
 #[cfg(feature = "read")]
 impl Display for ReadError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             ReadError::SchemaMismatch => f.write_str("The expected schema did not match that in the document."),
             ReadError::InvalidFormat => f.write_str("The format was not a valid Tree-Buf"),
+            ReadError::UnsupportedFormatVersion(version) => write!(f, "The format version {} is not supported", version),
+            ReadError::LimitExceeded(limit) => write!(f, "The document exceeded the {:?} limit", limit),
+            #[cfg(feature = "diagnostics")]
+            ReadError::Context(context) => {
+                Display::fmt(&context.error, f)?;
+                if !context.path.is_empty() {
+                    write!(f, " At: {}", context.path())?;
+                }
+                if let Some(expected) = context.expected {
+                    write!(f, " Expected: {}", expected)?;
+                }
+                if let Some(found) = context.found {
+                    write!(f, " Found: {}", found)?;
+                }
+                if let Some(offset) = context.offset {
+                    write!(f, " Offset: {}", offset)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -34,7 +236,3 @@ impl From<std::str::Utf8Error> for ReadError {
         ReadError::InvalidFormat
     }
 }
-
-
-
-
@@ -0,0 +1,61 @@
+//! An optional header at the start of a document, which identifies it as tree-buf
+//! and records the version of the format it was written with.
+//!
+//! Documents without a header are assumed to be written with the current format version.
+
+use crate::prelude::*;
+
+/// Marks the start of a header. The first byte is not a valid RootTypeId,
+/// so a document with a header cannot be confused with one without.
+pub const MAGIC: [u8; 4] = [0xFF, b'T', b'B', b'F'];
+
+/// The version of the format written by default.
+/// This is incremented whenever a change is made which older readers would not understand.
+/// When targeting an older version, what was introduced since is written some other way.
+///
+/// | Version | Introduced |
+/// |---------|------------|
+/// | 1 | The header |
+/// | 2 | 128 bit integers |
+/// | 3 | Blobs |
+/// | 4 | Decimals |
+/// | 5 | Delta encoding of integers |
+/// | 6 | Bit packing, and frame of reference with or without patching, of integers |
+/// | 7 | Simple-8b encoding of integers |
+/// | 8 | RLE and dictionary encoding of floats |
+/// | 9 | Byte stream split encoding of floats |
+pub const FORMAT_VERSION: u8 = 9;
+
+/// The oldest version of the format which can be read or written.
+pub const MIN_FORMAT_VERSION: u8 = 1;
+
+#[cfg(feature = "write")]
+pub fn write_header(options: &impl EncodeOptions, bytes: &mut Vec<u8>) {
+    // Versions outside of the supported range are written as the nearest supported version,
+    // which is also what the encoders produce for them.
+    let version = options.format_version().clamp(MIN_FORMAT_VERSION, FORMAT_VERSION);
+    if options.header() {
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(version);
+    }
+}
+
+#[cfg(feature = "read")]
+pub fn has_header(bytes: &[u8]) -> bool {
+    bytes.starts_with(&MAGIC)
+}
+
+/// Reads the header, if any, returning the format version of the document.
+#[cfg(feature = "read")]
+pub fn read_header(bytes: &[u8], offset: &mut usize) -> ReadResult<u8> {
+    if !has_header(bytes) {
+        return Ok(FORMAT_VERSION);
+    }
+    *offset += MAGIC.len();
+    let version = *bytes.get(*offset).ok_or(ReadError::InvalidFormat)?;
+    *offset += 1;
+    if !(MIN_FORMAT_VERSION..=FORMAT_VERSION).contains(&version) {
+        return Err(ReadError::UnsupportedFormatVersion(version));
+    }
+    Ok(version)
+}
@@ -0,0 +1,115 @@
+//! Limits on the resources used while reading, so that a corrupt or hostile document
+//! results in an error rather than running out of memory or overflowing the stack.
+//!
+//! The limits are set with the max_* methods of `DecodeOptions`.
+
+use crate::prelude::*;
+use std::convert::TryFrom;
+use std::mem::size_of;
+
+/// The limit in the `DecodeOptions` which a document exceeded.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Limit {
+    Depth,
+    CollectionLength,
+    StringLength,
+    Allocation,
+}
+
+#[cfg(feature = "read")]
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    max_depth: usize,
+    max_collection_length: usize,
+    max_string_length: usize,
+    max_allocation: usize,
+    depth: usize,
+}
+
+#[cfg(feature = "read")]
+impl Limits {
+    #[inline]
+    pub fn new(options: &impl DecodeOptions) -> Self {
+        Self {
+            max_depth: options.max_depth(),
+            max_collection_length: options.max_collection_length(),
+            max_string_length: options.max_string_length(),
+            max_allocation: options.max_allocation(),
+            depth: 0,
+        }
+    }
+
+    /// The limits for a branch nested one level deeper in the document.
+    #[inline]
+    pub fn nested(self) -> ReadResult<Self> {
+        if self.depth >= self.max_depth {
+            return Err(ReadError::LimitExceeded(Limit::Depth));
+        }
+        Ok(Self { depth: self.depth + 1, ..self })
+    }
+
+    #[inline]
+    pub fn length(&self, len: usize) -> ReadResult<usize> {
+        if len > self.max_collection_length {
+            return Err(ReadError::LimitExceeded(Limit::CollectionLength));
+        }
+        Ok(len)
+    }
+
+    /// Checks the length of a collection of `T`, and the memory needed to hold it.
+    #[inline]
+    pub fn collection<T>(&self, len: usize) -> ReadResult<usize> {
+        self.length(len)?;
+        match len.checked_mul(size_of::<T>()) {
+            Some(size) if size <= self.max_allocation => Ok(len),
+            _ => Err(ReadError::LimitExceeded(Limit::Allocation)),
+        }
+    }
+
+    /// Checks the memory needed to hold bytes which are expanded while reading, eg: by decompression.
+    #[inline]
+    pub fn allocation(&self, size: usize) -> ReadResult<usize> {
+        if size > self.max_allocation {
+            return Err(ReadError::LimitExceeded(Limit::Allocation));
+        }
+        Ok(size)
+    }
+
+    /// Checks the length of each item in a collection of collections of `T`.
+    pub fn collections<T>(&self, lens: &[u64]) -> ReadResult<()> {
+        for len in lens {
+            self.collection::<T>(usize::try_from(*len).map_err(|_| ReadError::LimitExceeded(Limit::CollectionLength))?)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the length of a collection of `T` which is expanded from runs.
+    pub fn runs<T>(&self, runs: &[u64]) -> ReadResult<usize> {
+        let mut len = 0usize;
+        for run in runs {
+            len = usize::try_from(*run)
+                .ok()
+                .and_then(|run| run.checked_add(1))
+                .and_then(|run| len.checked_add(run))
+                .ok_or(ReadError::LimitExceeded(Limit::CollectionLength))?;
+        }
+        self.collection::<T>(len)
+    }
+
+    #[inline]
+    pub fn string(&self, len: usize) -> ReadResult<usize> {
+        if len > self.max_string_length {
+            return Err(ReadError::LimitExceeded(Limit::StringLength));
+        }
+        Ok(len)
+    }
+}
+
+/// Like `Vec::with_capacity`, but returns an error rather than aborting when the
+/// memory can't be allocated. The capacity usually comes from a length in the document.
+#[cfg(feature = "read")]
+pub fn try_vec_with_capacity<T>(len: usize) -> ReadResult<Vec<T>> {
+    let mut v = Vec::new();
+    v.try_reserve_exact(len).map_err(|_| ReadError::LimitExceeded(Limit::Allocation))?;
+    Ok(v)
+}
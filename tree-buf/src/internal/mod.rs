@@ -7,6 +7,8 @@ pub mod branch;
 #[macro_use]
 pub mod encodings;
 pub mod error;
+pub mod header;
+pub mod limits;
 pub mod options;
 pub mod parallel;
 pub mod reader_writer;
@@ -17,6 +19,8 @@ pub mod markers;
 pub use {
     branch::*,
     encodings::*,
+    header::*,
+    limits::*,
     options::*,
     parallel::*,
     reader_writer::*,
@@ -1,3 +1,5 @@
+use crate::internal::header::FORMAT_VERSION;
+
 // https://docs.rs/ndarray-zfp-rs/0.1.0/ndarray_zfp_rs/trait.Zfp.htmls
 
 // TODO: This would be the place to specify in-place padded/aligned encoding when desired.
@@ -43,11 +45,19 @@ macro_rules! options {
 }
 
 options!(EncodeOptions, EncodeOptionsDefault, EncodeOptionsOverride, EncodeOptionsHierarchy, {
-    lossy_float_tolerance: Option<i32> = None
+    lossy_float_tolerance: Option<i32> = None,
+    header: bool = false,
+    format_version: u8 = FORMAT_VERSION,
+    blob_compression: bool = true
 });
 
 options!(DecodeOptions, DecodeOptionsDefault, DecodeOptionsOverride, DecodeOptionsHierarchy, {
-    parallel: bool = true
+    parallel: bool = true,
+    require_header: bool = false,
+    max_depth: usize = 256,
+    max_collection_length: usize = 1 << 28,
+    max_string_length: usize = usize::MAX,
+    max_allocation: usize = 1 << 30
 });
 
 pub struct EnableParallel;
@@ -82,6 +92,83 @@ impl EncodeOptionsOverride for LossyFloatTolerance {
     }
 }
 
+/// Starts the document with a header identifying it as tree-buf along with the format version.
+pub struct Header;
+impl EncodeOptionsOverride for Header {
+    #[inline(always)]
+    fn header(&self) -> Option<bool> {
+        Some(true)
+    }
+}
+
+/// Writes an older version of the format, for readers using an older version of tree-buf.
+/// Encodings which were introduced after that version are not used.
+/// Versions outside of `MIN_FORMAT_VERSION..=FORMAT_VERSION` write the nearest supported version.
+pub struct TargetFormatVersion(pub u8);
+impl EncodeOptionsOverride for TargetFormatVersion {
+    #[inline(always)]
+    fn format_version(&self) -> Option<u8> {
+        Some(self.0)
+    }
+}
+
+/// Writes blobs as they are, without trying to compress them.
+/// This is useful when the payloads are already compressed (eg: images).
+pub struct DisableBlobCompression;
+impl EncodeOptionsOverride for DisableBlobCompression {
+    #[inline(always)]
+    fn blob_compression(&self) -> Option<bool> {
+        Some(false)
+    }
+}
+
+/// Rejects documents which do not start with a header.
+pub struct RequireHeader;
+impl DecodeOptionsOverride for RequireHeader {
+    #[inline(always)]
+    fn require_header(&self) -> Option<bool> {
+        Some(true)
+    }
+}
+
+/// The deepest that branches may be nested in a document.
+/// This protects against overflowing the stack. The default is 256.
+/// Each level of recursion in a type like `struct Node { children: Vec<Node> }` takes about two.
+pub struct MaxDepth(pub usize);
+impl DecodeOptionsOverride for MaxDepth {
+    #[inline(always)]
+    fn max_depth(&self) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
+/// The most items that may be in any array or map. The default is 2^28.
+pub struct MaxCollectionLength(pub usize);
+impl DecodeOptionsOverride for MaxCollectionLength {
+    #[inline(always)]
+    fn max_collection_length(&self) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
+/// The most bytes that may be in any string.
+pub struct MaxStringLength(pub usize);
+impl DecodeOptionsOverride for MaxStringLength {
+    #[inline(always)]
+    fn max_string_length(&self) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
+/// The most bytes that may be allocated at once for any collection. The default is 1 GiB.
+pub struct MaxAllocation(pub usize);
+impl DecodeOptionsOverride for MaxAllocation {
+    #[inline(always)]
+    fn max_allocation(&self) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
 // TODO: Move the remainder here into the macro
 pub fn override_encode_options(options: impl EncodeOptions, overrides: impl EncodeOptionsOverride) -> impl EncodeOptions {
     EncodeOptionsHierarchy { fallback: options, overrides }
@@ -67,10 +67,12 @@ pub trait Writable: Sized {
     fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId;
 }
 
+/// The lifetime is that of the bytes being read, which allows
+/// for types like `&'a str` to borrow from the input rather than copy.
 #[cfg(feature = "read")]
-pub trait Readable: Sized {
-    type ReaderArray: ReaderArray<Read = Self>;
-    fn read(sticks: DynRootBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self>;
+pub trait Readable<'a>: Sized {
+    type ReaderArray: ReaderArray<'a, Read = Self>;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self>;
 }
 
 // TODO: Introduce a separate "Scratch" type to make eg: WriterArray re-usable.
@@ -86,20 +88,29 @@ pub trait WriterArray<T: ?Sized>: Default {
 }
 
 #[cfg(feature = "read")]
-pub trait ReaderArray: Sized + Send {
-    type Error: CoercibleWith<ReadError> + CoercibleWith<Never>;
+pub trait ReaderArray<'a>: Sized + Send {
+    // The Into bound allows for generic code to convert errors without
+    // requiring a `ReadError: From<T::Error>` bound, which can't be
+    // proven for all lifetimes when reading owned data.
+    type Error: CoercibleWith<ReadError> + CoercibleWith<Never> + Into<ReadError>;
     type Read;
-    // TODO: It would be nice to be able to keep reference to the original byte array, especially for reading strings.
-    // I think that may require GAT though the way things are setup so come back to this later.
-    fn new(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self>;
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self>;
     fn read_next(&mut self) -> Result<Self::Read, Self::Error>;
+    /// How many values are left in the branch, if known. Reading past them gives
+    /// default values, so this is only used to cap the memory reserved for a length in the document.
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
 }
 
-pub trait InfallibleReaderArray: Sized {
+pub trait InfallibleReaderArray<'a>: Sized {
     type Read;
     /// This isn't actually infallable, it's just named this to not conflict.
-    fn new_infallible(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self>;
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self>;
     fn read_next_infallible(&mut self) -> Self::Read;
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// This trait exists to first reduce a little bit of boilerplate for the common
@@ -108,12 +119,12 @@ pub trait InfallibleReaderArray: Sized {
 /// is impossible. Putting the inline here instead of on a read_next of a ReaderArray
 /// implementation allows for not necessarily inlining what may be a larger method.
 /// It may not be necessary, but why not.
-impl<T: InfallibleReaderArray + Send> ReaderArray for T {
-    type Read = <Self as InfallibleReaderArray>::Read;
+impl<'a, T: InfallibleReaderArray<'a> + Send> ReaderArray<'a> for T {
+    type Read = <Self as InfallibleReaderArray<'a>>::Read;
     type Error = Never;
 
     #[inline(always)]
-    fn new(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
         InfallibleReaderArray::new_infallible(sticks, options)
     }
 
@@ -121,4 +132,9 @@ impl<T: InfallibleReaderArray + Send> ReaderArray for T {
     fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
         Ok(InfallibleReaderArray::read_next_infallible(self))
     }
+
+    #[inline(always)]
+    fn remaining(&self) -> Option<usize> {
+        InfallibleReaderArray::remaining(self)
+    }
 }
@@ -0,0 +1,78 @@
+use crate::prelude::*;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
+use std::rc::Rc;
+use std::sync::Arc;
+
+// These collections and slices are written as arrays, the same as Vec, so that
+// any of them may be read as another. Eg: a HashSet may be read as a Vec, or a Vec
+// as a BTreeSet. Reading an array with duplicate items into a set keeps one of each.
+
+#[cfg(feature = "read")]
+pub struct CollectionArrayReader<T, C> {
+    inner: Option<VecArrayReader<T>>,
+    _marker: Unowned<C>,
+}
+
+#[cfg(feature = "read")]
+impl<'a, T: ReaderArray<'a>, C: FromIterator<T::Read>> ReaderArray<'a> for CollectionArrayReader<T, C> {
+    type Read = C;
+    type Error = ReadError;
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+        Ok(CollectionArrayReader {
+            inner: ReaderArray::new(sticks, options)?,
+            _marker: Unowned::new(),
+        })
+    }
+    fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
+        // TODO: (Performance) Read directly into the collection rather than through a Vec
+        Ok(self.inner.read_next()?.into_iter().collect())
+    }
+    fn remaining(&self) -> Option<usize> {
+        self.inner.remaining()
+    }
+}
+
+macro_rules! impl_collection {
+    (impl<$($lifetime:lifetime,)? T $(, $S:ident)?> for $Collection:ty $(where $($bounds:tt)+)?) => {
+        #[cfg(feature = "write")]
+        impl<$($lifetime,)? T: Writable $(, $S)?> Writable for $Collection $(where $($bounds)+)? {
+            type WriterArray = VecArrayWriter<T::WriterArray>;
+            fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+                profile!("write_root");
+                write_root_items(self.iter(), stream)
+            }
+        }
+
+        #[cfg(feature = "write")]
+        impl<$($lifetime,)? T: Writable $(, $S)?> WriterArray<$Collection> for VecArrayWriter<T::WriterArray> $(where $($bounds)+)? {
+            fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b $Collection) {
+                self.buffer_items(value.iter());
+            }
+            fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+                <Self as WriterArray<Vec<T>>>::flush(self, stream)
+            }
+        }
+
+        #[cfg(feature = "read")]
+        impl<'a, $($lifetime,)? T: Readable<'a> $(, $S)?> Readable<'a> for $Collection $(where $($bounds)+)? {
+            type ReaderArray = CollectionArrayReader<T::ReaderArray, Self>;
+            fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!("Readable::read");
+                Ok(Vec::<T>::read(sticks, options)?.into_iter().collect())
+            }
+        }
+    };
+}
+
+impl_collection!(impl<T> for VecDeque<T>);
+impl_collection!(impl<T> for LinkedList<T>);
+impl_collection!(impl<T, S> for HashSet<T, S> where T: Hash + Eq, S: Default + BuildHasher);
+impl_collection!(impl<T> for BTreeSet<T> where T: Ord);
+impl_collection!(impl<T> for Box<[T]>);
+impl_collection!(impl<T> for Rc<[T]>);
+impl_collection!(impl<T> for Arc<[T]>);
+impl_collection!(impl<'c, T> for Cow<'c, [T]> where T: Clone);
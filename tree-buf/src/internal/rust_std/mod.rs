@@ -1,5 +1,7 @@
+mod collections;
 mod smart_pointers;
 mod usize;
 
 pub use self::usize::*;
+pub use collections::*;
 pub use smart_pointers::*;
@@ -1,55 +1,72 @@
 use crate::prelude::*;
 use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
 
 // TODO: impl Writable for () {
-#[cfg(feature = "write")]
-#[derive(Default)]
-pub struct BoxWriterArray<T> {
-    inner: T,
-}
+// Smart pointers are written the same as the value they point to.
+// Rc is not Send, so it can't be read where values are read in parallel,
+// such as the fields of a derived struct. Arc can be used there instead.
+// The inner arrays are boxed as well, so that recursive
+// types like enum Expr { Neg(Box<Expr>), .. } have a finite size.
+macro_rules! impl_smart_pointer {
+    ($Pointer:ident, $WriterArray:ident, $ReaderArray:ident) => {
+        #[cfg(feature = "write")]
+        #[derive(Default)]
+        pub struct $WriterArray<T> {
+            inner: Box<T>,
+        }
 
-#[cfg(feature = "write")]
-impl<T: Writable> Writable for Box<T> {
-    type WriterArray = BoxWriterArray<T::WriterArray>;
-    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
-        profile!("write_root");
-        self.deref().write_root(stream)
-    }
-}
+        #[cfg(feature = "write")]
+        impl<T: Writable> Writable for $Pointer<T> {
+            type WriterArray = $WriterArray<T::WriterArray>;
+            fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+                profile!("write_root");
+                self.deref().write_root(stream)
+            }
+        }
 
-#[cfg(feature = "read")]
-pub struct BoxReaderArray<T> {
-    inner: T,
-}
+        #[cfg(feature = "read")]
+        pub struct $ReaderArray<T> {
+            inner: Box<T>,
+        }
 
-#[cfg(feature = "read")]
-impl<T: Readable> Readable for Box<T> {
-    type ReaderArray = BoxReaderArray<T::ReaderArray>;
-    fn read(sticks: DynRootBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
-        profile!("Readable::read");
-        Ok(Box::new(T::read(sticks, options)?))
-    }
-}
+        #[cfg(feature = "read")]
+        impl<'a, T: Readable<'a>> Readable<'a> for $Pointer<T> {
+            type ReaderArray = $ReaderArray<T::ReaderArray>;
+            fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!("Readable::read");
+                Ok($Pointer::new(T::read(sticks, options)?))
+            }
+        }
 
-#[cfg(feature = "write")]
-impl<T: Writable> WriterArray<Box<T>> for BoxWriterArray<T::WriterArray> {
-    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b Box<T>) {
-        self.inner.buffer(&value)
-    }
-    fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
-        self.inner.flush(stream)
-    }
-}
+        #[cfg(feature = "write")]
+        impl<T: Writable> WriterArray<$Pointer<T>> for $WriterArray<T::WriterArray> {
+            fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b $Pointer<T>) {
+                self.inner.buffer(&value)
+            }
+            fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+                self.inner.flush(stream)
+            }
+        }
 
-#[cfg(feature = "read")]
-impl<T: ReaderArray> ReaderArray for BoxReaderArray<T> {
-    type Read = Box<T::Read>;
-    type Error = T::Error;
-    fn new(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
-        profile!("ReaderArray::new");
-        Ok(BoxReaderArray { inner: T::new(sticks, options)? })
-    }
-    fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
-        Ok(Box::new(self.inner.read_next()?))
-    }
+        #[cfg(feature = "read")]
+        impl<'a, T: ReaderArray<'a>> ReaderArray<'a> for $ReaderArray<T> {
+            type Read = $Pointer<T::Read>;
+            type Error = T::Error;
+            fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!("ReaderArray::new");
+                Ok($ReaderArray {
+                    inner: Box::new(T::new(sticks, options)?),
+                })
+            }
+            fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
+                Ok($Pointer::new(self.inner.read_next()?))
+            }
+        }
+    };
 }
+
+impl_smart_pointer!(Box, BoxWriterArray, BoxReaderArray);
+impl_smart_pointer!(Rc, RcWriterArray, RcReaderArray);
+impl_smart_pointer!(Arc, ArcWriterArray, ArcReaderArray);
@@ -1,36 +1,90 @@
 #[cfg(feature = "read")]
-use crate::internal::encodings::varint::{decode_prefix_varint, encode_prefix_varint};
+use crate::internal::encodings::varint::decode_prefix_varint;
+#[cfg(feature = "write")]
+use crate::internal::encodings::varint::encode_prefix_varint;
 use crate::prelude::*;
+use std::convert::{TryFrom, TryInto};
 use std::vec::IntoIter;
 
-#[cfg(feature = "read")]
-impl InfallibleReaderArray for IntoIter<usize> {
-    type Read = usize;
-    fn new_infallible(_sticks: DynArrayBranch<'_>, _options: &impl DecodeOptions) -> ReadResult<Self> {
-        todo!("usize ReaderArray new");
-    }
-    fn read_next_infallible(&mut self) -> Self::Read {
-        self.next().unwrap_or_default()
-    }
+// usize and isize are written as u64 and i64, so that the format
+// does not depend on the width of the platform which wrote it.
+// When reading, values which don't fit the platform are a schema mismatch,
+// just like reading a u64 into a u32.
+macro_rules! impl_platform_width {
+    ($Ty:ty, $Wide:ty) => {
+        #[cfg(feature = "write")]
+        impl Writable for $Ty {
+            type WriterArray = Vec<$Ty>;
+            fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+                (*self as $Wide).write_root(stream)
+            }
+        }
+
+        #[cfg(feature = "write")]
+        impl WriterArray<$Ty> for Vec<$Ty> {
+            fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b $Ty) {
+                self.push(*value);
+            }
+            fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+                profile!("WriterArray::flush");
+                // TODO: (Performance) Use second-stack
+                let wide = self.iter().map(|v| *v as $Wide).collect::<Vec<_>>();
+                wide.flush(stream)
+            }
+        }
+
+        #[cfg(feature = "read")]
+        impl<'a> Readable<'a> for $Ty {
+            type ReaderArray = IntoIter<$Ty>;
+            fn read(sticks: DynRootBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!("Readable::read");
+                match sticks {
+                    DynRootBranch::Integer(root_int) => match root_int {
+                        RootInteger::U(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                        RootInteger::S(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                        RootInteger::U128(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                        RootInteger::S128(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                    },
+                    other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
+                }
+            }
+        }
+
+        #[cfg(feature = "read")]
+        impl<'a> InfallibleReaderArray<'a> for IntoIter<$Ty> {
+            type Read = $Ty;
+            fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!(Self::Read, "ReaderArray::new");
+                let found = sticks.type_name();
+                let values = <IntoIter<$Wide> as InfallibleReaderArray>::new_infallible(sticks, options).map_err(|e| match e.kind() {
+                    ReadError::SchemaMismatch => ReadError::schema_mismatch::<$Ty>(found),
+                    _ => e,
+                })?;
+                let values = values
+                    .map(<$Ty>::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| ReadError::schema_mismatch::<$Ty>("Integer"))?;
+                Ok(values.into_iter())
+            }
+            fn read_next_infallible(&mut self) -> Self::Read {
+                self.next().unwrap_or_default()
+            }
+            fn remaining(&self) -> Option<usize> {
+                Some(self.len())
+            }
+        }
+    };
 }
 
-// TODO: Come back to usize
-// TODO: Error check that the result fits in the platform size
+impl_platform_width!(usize, u64);
+impl_platform_width!(isize, i64);
+
 #[cfg(feature = "read")]
 pub fn read_usize(bytes: &[u8], offset: &mut usize) -> ReadResult<usize> {
-    Ok(decode_prefix_varint(bytes, offset)? as usize)
+    usize::try_from(decode_prefix_varint(bytes, offset)?).map_err(|_| ReadError::InvalidFormat)
 }
 
 #[cfg(feature = "write")]
 pub fn write_usize<O: EncodeOptions>(value: usize, stream: &mut WriterStream<'_, O>) {
     encode_prefix_varint(value as u64, stream.bytes);
 }
-
-/*
-impl Readable for usize {
-    type ReaderArray = IntoIter<usize>;
-    fn read(sticks: DynRootBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
-        Ok(u64::read(sticks, options)? as Self)
-    }
-}
-*/
@@ -6,66 +6,70 @@ impl<T: Writable> Writable for Vec<T> {
     type WriterArray = VecArrayWriter<T::WriterArray>;
     fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
         profile!("write_root");
-        match self.len() {
-            0 => RootTypeId::Array0,
-            1 => {
-                stream.write_with_id(|stream| (&self[0]).write_root(stream));
-                RootTypeId::Array1
-            }
-            _ => {
-                // TODO: Seems kind of redundant to have both the array len,
-                // and the bytes len. Though, it's not for obvious reasons.
-                // Maybe sometimes we can infer from context. Eg: bool always
-                // requires the same number of bits per item
-                write_usize(self.len(), stream);
-
-                // TODO: When there are types that are already
-                // primitive (eg: Vec<f64>) it doesn't make sense
-                // to buffer at this level. Specialization may
-                // be useful here.
-                //
-                // TODO: See below, and just call buffer on the vec
-                // and flush it!
-                let mut writer = T::WriterArray::default();
-                for item in self {
-                    writer.buffer(item);
-                }
+        write_root_items(self.iter(), stream)
+    }
+}
 
-                stream.write_with_id(|stream| writer.flush(stream));
+/// Writes a sequence of items as an array. Shared by the collections
+/// which are written in the same way as Vec, so that they may be read as one another.
+#[cfg(feature = "write")]
+pub(crate) fn write_root_items<'a, T: Writable + 'a, O: EncodeOptions>(mut items: impl ExactSizeIterator<Item = &'a T>, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+    match items.len() {
+        0 => RootTypeId::Array0,
+        1 => {
+            let item = items.next().unwrap();
+            stream.write_with_id(|stream| item.write_root(stream));
+            RootTypeId::Array1
+        }
+        len => {
+            // TODO: Seems kind of redundant to have both the array len,
+            // and the bytes len. Though, it's not for obvious reasons.
+            // Maybe sometimes we can infer from context. Eg: bool always
+            // requires the same number of bits per item
+            write_usize(len, stream);
 
-                RootTypeId::ArrayN
+            // TODO: When there are types that are already
+            // primitive (eg: Vec<f64>) it doesn't make sense
+            // to buffer at this level. Specialization may
+            // be useful here.
+            //
+            // TODO: See below, and just call buffer on the vec
+            // and flush it!
+            let mut writer = T::WriterArray::default();
+            for item in items {
+                writer.buffer(item);
             }
+
+            stream.write_with_id(|stream| writer.flush(stream));
+
+            RootTypeId::ArrayN
         }
     }
 }
 
 #[cfg(feature = "read")]
-impl<T: Readable> Readable for Vec<T>
-// Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
-where
-    ReadError: From<<<T as Readable>::ReaderArray as ReaderArray>::Error>,
-{
+impl<'a, T: Readable<'a>> Readable<'a> for Vec<T> {
     type ReaderArray = Option<VecArrayReader<T::ReaderArray>>;
-    fn read(sticks: DynRootBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
         profile!("Readable::read");
         match sticks {
             DynRootBranch::Array0 => Ok(Vec::new()),
             DynRootBranch::Array1(inner) => {
-                let inner = T::read(*inner, options)?;
+                let inner = T::read(*inner, options).map_err(ReadError::in_element)?;
                 Ok(vec![inner])
             }
             DynRootBranch::Array { len, values } => {
-                let mut v = Vec::with_capacity(len);
+                let mut v = try_vec_with_capacity(Limits::new(options).collection::<T>(len)?)?;
                 // TODO: Some of what the code is actually doing here is silly.
                 // Actual ReaderArray's may be IntoIter, which moved out of a Vec
                 // that we wanted in the first place. Specialization here would be nice.
-                let mut reader = T::ReaderArray::new(values, options)?;
+                let mut reader = T::ReaderArray::new(values, options).map_err(ReadError::in_element)?;
                 for _ in 0..len {
-                    v.push(reader.read_next()?);
+                    v.push(reader.read_next().map_err(|e| Into::<ReadError>::into(e).in_element())?);
                 }
                 Ok(v)
             }
-            _ => Err(ReadError::SchemaMismatch),
+            other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
         }
     }
 }
@@ -75,18 +79,19 @@ where
 pub struct VecArrayWriter<T> {
     // TODO: usize
     len: <u64 as Writable>::WriterArray,
-    // Using Option here enables recursion when necessary.
-    values: Option<T>,
+    // Using Option and Box here enables recursion, since the
+    // WriterArray for a recursive type would otherwise contain itself.
+    values: Option<Box<T>>,
 }
 
 // TODO: usize
-enum FixedOrVariableLength {
+pub(crate) enum FixedOrVariableLength {
     Fixed(usize),
     Variable(IntoIter<u64>),
 }
 
 impl FixedOrVariableLength {
-    fn next(&mut self) -> usize {
+    pub(crate) fn next(&mut self) -> usize {
         match self {
             Self::Fixed(v) => *v,
             Self::Variable(i) => i.read_next_infallible() as usize,
@@ -97,7 +102,22 @@ impl FixedOrVariableLength {
 #[cfg(feature = "read")]
 pub struct VecArrayReader<T> {
     len: FixedOrVariableLength,
-    values: T,
+    // Boxed to allow for recursion. See also VecArrayWriter
+    values: Box<T>,
+}
+
+#[cfg(feature = "write")]
+impl<W> VecArrayWriter<W> {
+    pub(crate) fn buffer_items<'b, T: 'b>(&mut self, items: impl ExactSizeIterator<Item = &'b T>)
+    where
+        W: WriterArray<T>,
+    {
+        self.len.buffer(&(items.len() as u64));
+        let values = self.values.get_or_insert_with(Default::default);
+        for item in items {
+            values.buffer(item);
+        }
+    }
 }
 
 #[cfg(feature = "write")]
@@ -110,11 +130,7 @@ impl<T: Writable> WriterArray<Vec<T>> for VecArrayWriter<T::WriterArray> {
         // I feel though that somehow this outer buffer type
         // could fix the specialization problem above for single-vec
         // values.
-        self.len.buffer(&(value.len() as u64));
-        let values = self.values.get_or_insert_with(Default::default);
-        for item in value {
-            values.buffer(item);
-        }
+        self.buffer_items(value.iter());
     }
     fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
         profile!("flush");
@@ -137,41 +153,55 @@ impl<T: Writable> WriterArray<Vec<T>> for VecArrayWriter<T::WriterArray> {
 }
 
 #[cfg(feature = "read")]
-impl<T: ReaderArray> ReaderArray for Option<VecArrayReader<T>> {
+impl<'a, T: ReaderArray<'a>> ReaderArray<'a> for Option<VecArrayReader<T>> {
     type Read = Vec<T::Read>;
-    type Error = T::Error;
+    type Error = ReadError;
 
-    fn new(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
         profile!("ReaderArray::new");
 
         match sticks {
             DynArrayBranch::Array0 => Ok(None),
             DynArrayBranch::Array { len, values } => {
-                let (values, len) = parallel(|| T::new(*values, options), || <<u64 as Readable>::ReaderArray as ReaderArray>::new(*len, options), options);
-                let values = values?;
-                let len = FixedOrVariableLength::Variable(len?);
+                let (values, len) = parallel(|| T::new(*values, options), || <IntoIter<u64> as ReaderArray>::new(*len, options), options);
+                let values = Box::new(values.map_err(ReadError::in_element)?);
+                let len = len?;
+                Limits::new(options).collections::<T::Read>(len.as_slice())?;
+                let len = FixedOrVariableLength::Variable(len);
                 Ok(Some(VecArrayReader { len, values }))
             }
             DynArrayBranch::ArrayFixed { len, values } => Ok(if len == 0 {
                 None
             } else {
-                let len = FixedOrVariableLength::Fixed(len);
-                let values = T::new(*values, options)?;
+                let len = FixedOrVariableLength::Fixed(Limits::new(options).collection::<T::Read>(len)?);
+                let values = Box::new(T::new(*values, options).map_err(ReadError::in_element)?);
                 Some(VecArrayReader { len, values })
             }),
-            _ => Err(ReadError::SchemaMismatch),
+            other => Err(ReadError::schema_mismatch::<Self::Read>(other.type_name())),
         }
     }
     fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
         if let Some(inner) = self {
             let len = inner.len.next();
-            let mut result = Vec::with_capacity(len);
+            // The length was checked against the limits in new, but the values
+            // may still run out early in a corrupt document.
+            let capacity = inner.values.remaining().map_or(len, |remaining| len.min(remaining));
+            let mut result = try_vec_with_capacity(capacity)?;
             for _ in 0..len {
-                result.push(inner.values.read_next()?);
+                result.push(inner.values.read_next().map_err(Into::into)?);
             }
             Ok(result)
         } else {
             Ok(Vec::new())
         }
     }
+    fn remaining(&self) -> Option<usize> {
+        match self {
+            Some(VecArrayReader {
+                len: FixedOrVariableLength::Variable(len),
+                ..
+            }) => Some(len.len()),
+            _ => None,
+        }
+    }
 }
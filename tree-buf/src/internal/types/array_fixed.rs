@@ -47,16 +47,15 @@ macro_rules! impl_fixed {
                 }
             }
 
-            // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
             #[cfg(feature = "read")]
-            impl<T: Readable + Sized> Readable for [T; $size] where ReadError : From<<T::ReaderArray as ReaderArray>::Error> {
+            impl<'a, T: Readable<'a> + Sized> Readable<'a> for [T; $size] {
                 type ReaderArray = ArrayReader<T::ReaderArray>;
-                fn read(sticks: DynRootBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
                     profile!("Readable::read");
                     match sticks {
                         DynRootBranch::Array0 => {
                             if $size != 0 {
-                                return Err(ReadError::SchemaMismatch);
+                                return Err(ReadError::schema_mismatch::<Self>("Array"));
                             } else {
                                 let data: [MaybeUninit<T>; $size] = unsafe {
                                     MaybeUninit::uninit().assume_init()
@@ -69,9 +68,9 @@ macro_rules! impl_fixed {
                         },
                         DynRootBranch::Array1(inner) => {
                             if $size != 1 {
-                                return Err(ReadError::SchemaMismatch);
+                                return Err(ReadError::schema_mismatch::<Self>("Array"));
                             }
-                            let inner = T::read(*inner, options)?;
+                            let inner = T::read(*inner, options).map_err(ReadError::in_element)?;
 
                             let mut data: [MaybeUninit<T>; $size] = unsafe {
                                 MaybeUninit::uninit().assume_init()
@@ -83,20 +82,20 @@ macro_rules! impl_fixed {
                         }
                         DynRootBranch::Array { len, values } => {
                             if len != $size {
-                                return Err(ReadError::SchemaMismatch);
+                                return Err(ReadError::schema_mismatch::<Self>("Array"));
                             }
-                            let mut reader = T::ReaderArray::new(values, options)?;
+                            let mut reader = T::ReaderArray::new(values, options).map_err(ReadError::in_element)?;
                             let mut data: [MaybeUninit<T>; $size] = unsafe {
                                 MaybeUninit::uninit().assume_init()
                             };
 
                             for elem in &mut data[..] {
-                                *elem = MaybeUninit::new(reader.read_next()?);
+                                *elem = MaybeUninit::new(reader.read_next().map_err(|e| Into::<ReadError>::into(e).in_element())?);
                             }
 
                             Ok(unsafe { transmute(data) })
                         }
-                        _ => Err(ReadError::SchemaMismatch),
+                        other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
                     }
                 }
             }
@@ -140,21 +139,21 @@ macro_rules! impl_fixed {
             }
 
             #[cfg(feature = "read")]
-            impl<T: ReaderArray> ReaderArray for ArrayReader<T> {
+            impl<'a, T: ReaderArray<'a>> ReaderArray<'a> for ArrayReader<T> {
                 type Read = [T::Read; $size];
                 type Error = T::Error;
-                fn new(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
                     profile!("ReaderArray::new");
 
                     match sticks {
                         DynArrayBranch::ArrayFixed { len, values } => {
                             if len != $size {
-                                return Err(ReadError::SchemaMismatch);
+                                return Err(ReadError::schema_mismatch::<Self::Read>("Array"));
                             }
-                            let values = T::new(*values, options)?;
+                            let values = T::new(*values, options).map_err(ReadError::in_element)?;
                             Ok(ArrayReader { values })
                         }
-                        _ => Err(ReadError::SchemaMismatch),
+                        other => Err(ReadError::schema_mismatch::<Self::Read>(other.type_name())),
                     }
                 }
                 fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
@@ -0,0 +1,221 @@
+#[cfg(feature = "write")]
+use crate::internal::encodings::lz4;
+#[cfg(feature = "write")]
+use crate::internal::encodings::varint::size_for_varint;
+use crate::prelude::*;
+#[cfg(feature = "read")]
+use std::borrow::Cow;
+use std::ops::Deref;
+#[cfg(feature = "read")]
+use std::vec::IntoIter;
+
+/// Opaque bytes, eg: an image or a message in some other format.
+///
+/// Unlike `Vec<u8>`, which is written as an array of integers, the bytes are
+/// written as they are, and may be compressed with LZ4 when that makes them smaller.
+/// See also `options::DisableBlobCompression`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Blob(pub Vec<u8>);
+
+impl From<Vec<u8>> for Blob {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        Blob(value)
+    }
+}
+
+impl From<Blob> for Vec<u8> {
+    #[inline]
+    fn from(value: Blob) -> Self {
+        value.0
+    }
+}
+
+impl Deref for Blob {
+    type Target = [u8];
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// Blobs which are smaller than this are not worth the attempt to compress.
+#[cfg(feature = "write")]
+const MIN_COMPRESS_LEN: usize = 64;
+
+/// Compresses the bytes, unless that would not make them smaller.
+/// The result does not include the varint for the decompressed length, but accounts for it.
+#[cfg(feature = "write")]
+fn compress_blob(bytes: &[u8], options: &impl EncodeOptions) -> Option<Vec<u8>> {
+    if !options.blob_compression() || bytes.len() < MIN_COMPRESS_LEN {
+        return None;
+    }
+    let compressed = lz4::compress(bytes);
+    if compressed.len() + size_for_varint(bytes.len() as u64) < bytes.len() {
+        Some(compressed)
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "write")]
+pub(crate) fn write_root_blob<O: EncodeOptions>(value: &[u8], stream: &mut WriterStream<'_, O>) -> RootTypeId {
+    // Introduced in version 3 of the format. Before that, the bytes are written as a Vec<u8>
+    if stream.options.format_version() < 3 {
+        return write_root_items(value.iter(), stream);
+    }
+    if let Some(compressed) = compress_blob(value, stream.options) {
+        write_usize(value.len(), stream);
+        write_usize(compressed.len(), stream);
+        stream.bytes.extend_from_slice(&compressed);
+        RootTypeId::BlobLz4
+    } else {
+        write_usize(value.len(), stream);
+        stream.bytes.extend_from_slice(value);
+        RootTypeId::Blob
+    }
+}
+
+#[cfg(feature = "write")]
+impl Writable for Blob {
+    type WriterArray = BlobArrayWriter;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        profile!("write_root");
+        write_root_blob(self, stream)
+    }
+}
+
+#[cfg(feature = "write")]
+#[derive(Debug, Default)]
+pub struct BlobArrayWriter {
+    len: Vec<u64>,
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "write")]
+impl BlobArrayWriter {
+    pub(crate) fn buffer_bytes(&mut self, value: &[u8]) {
+        self.len.push(value.len() as u64);
+        self.bytes.extend_from_slice(value);
+    }
+}
+
+#[cfg(feature = "write")]
+impl WriterArray<Blob> for BlobArrayWriter {
+    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b Blob) {
+        self.buffer_bytes(value);
+    }
+    fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+        profile!("WriterArray::flush");
+        let Self { len, bytes } = self;
+        if len.is_empty() {
+            return ArrayTypeId::Void;
+        }
+        // See also write_root_blob
+        if stream.options.format_version() < 3 {
+            let mut writer = VecArrayWriter::<<u8 as Writable>::WriterArray>::default();
+            let mut offset = 0;
+            for len in len {
+                let end = offset + len as usize;
+                writer.buffer_items(bytes[offset..end].iter());
+                offset = end;
+            }
+            return WriterArray::<Vec<u8>>::flush(writer, stream);
+        }
+        stream.write_with_id(|stream| len.flush(stream));
+        if let Some(compressed) = compress_blob(&bytes, stream.options) {
+            write_usize(bytes.len(), stream);
+            stream.write_with_len(|stream| stream.bytes.extend_from_slice(&compressed));
+            ArrayTypeId::BlobLz4
+        } else {
+            stream.write_with_len(|stream| stream.bytes.extend_from_slice(&bytes));
+            ArrayTypeId::Blob
+        }
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> Readable<'a> for Blob {
+    type ReaderArray = BlobArrayReader<'a>;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        match sticks {
+            DynRootBranch::Blob(bytes) => Ok(Blob(bytes.decompress(&Limits::new(options))?.into_owned())),
+            // See also write_root_blob
+            DynRootBranch::Array0 | DynRootBranch::Array1(_) | DynRootBranch::Array { .. } => Ok(Blob(Vec::read(sticks, options)?)),
+            other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
+        }
+    }
+}
+
+#[cfg(feature = "read")]
+pub struct BlobArrayReader<'a> {
+    len: IntoIter<u64>,
+    bytes: Cow<'a, [u8]>,
+    offset: usize,
+}
+
+#[cfg(feature = "read")]
+impl<'a> InfallibleReaderArray<'a> for BlobArrayReader<'a> {
+    type Read = Blob;
+
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+        match sticks {
+            DynArrayBranch::Void | DynArrayBranch::Array0 => Ok(BlobArrayReader {
+                len: Vec::new().into_iter(),
+                bytes: Cow::Borrowed(&[]),
+                offset: 0,
+            }),
+            DynArrayBranch::Blob { len, bytes } => {
+                let len = <IntoIter<u64> as InfallibleReaderArray>::new_infallible(*len, options)?;
+                let bytes = bytes.decompress(&Limits::new(options))?;
+                // Checking the lengths up front means that reading each blob can't fail.
+                let total = len.as_slice().iter().try_fold(0u64, |total, l| total.checked_add(*l));
+                if total != Some(bytes.len() as u64) {
+                    return Err(ReadError::InvalidFormat);
+                }
+                Ok(BlobArrayReader { len, bytes, offset: 0 })
+            }
+            // See also WriterArray::flush for BlobArrayWriter
+            DynArrayBranch::Array { len, values } => {
+                let (len, bytes) = parallel(
+                    || <IntoIter<u64> as InfallibleReaderArray>::new_infallible(*len, options),
+                    || <IntoIter<u8> as InfallibleReaderArray>::new_infallible(*values, options),
+                    options,
+                );
+                let (len, bytes) = (len?, bytes?.collect::<Vec<_>>());
+                let total = len.as_slice().iter().try_fold(0u64, |total, l| total.checked_add(*l));
+                if total != Some(bytes.len() as u64) {
+                    return Err(ReadError::InvalidFormat);
+                }
+                Ok(BlobArrayReader {
+                    len,
+                    bytes: Cow::Owned(bytes),
+                    offset: 0,
+                })
+            }
+            DynArrayBranch::ArrayFixed { len, values } => {
+                let bytes = <IntoIter<u8> as InfallibleReaderArray>::new_infallible(*values, options)?.collect::<Vec<_>>();
+                // Every blob has the same length, so the number of blobs is only known from the bytes.
+                let count = match len {
+                    0 => 0,
+                    len if bytes.len() % len == 0 => bytes.len() / len,
+                    _ => return Err(ReadError::InvalidFormat),
+                };
+                Ok(BlobArrayReader {
+                    len: vec![len as u64; count].into_iter(),
+                    bytes: Cow::Owned(bytes),
+                    offset: 0,
+                })
+            }
+            other => Err(ReadError::schema_mismatch::<Blob>(other.type_name())),
+        }
+    }
+    fn read_next_infallible(&mut self) -> Self::Read {
+        let len = self.len.next().unwrap_or_default() as usize;
+        let start = self.offset;
+        self.offset += len;
+        Blob(self.bytes[start..self.offset].to_vec())
+    }
+}
@@ -17,13 +17,13 @@ impl Writable for bool {
 }
 
 #[cfg(feature = "read")]
-impl Readable for bool {
+impl<'a> Readable<'a> for bool {
     type ReaderArray = IntoIter<bool>;
-    fn read(sticks: DynRootBranch<'_>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn read(sticks: DynRootBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
         profile!("Readable::read");
         match sticks {
             DynRootBranch::Boolean(v) => Ok(v),
-            _ => Err(ReadError::SchemaMismatch),
+            other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
         }
     }
 }
@@ -69,10 +69,10 @@ impl Compressor<bool> for RLEBoolCompressor {
 }
 
 #[cfg(feature = "read")]
-impl InfallibleReaderArray for IntoIter<bool> {
+impl<'a> InfallibleReaderArray<'a> for IntoIter<bool> {
     type Read = bool;
 
-    fn new_infallible(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
         profile!("ReaderArray::new");
 
         match sticks {
@@ -81,15 +81,19 @@ impl InfallibleReaderArray for IntoIter<bool> {
                     ArrayBool::Packed(bytes) => decode_packed_bool(&bytes).into_iter(),
                     ArrayBool::RLE(first, runs) => {
                         let runs = <u64 as Readable>::ReaderArray::new(*runs, options)?;
+                        Limits::new(options).runs::<bool>(runs.as_slice())?;
                         decode_rle_bool(runs, first)
                     }
                 };
                 Ok(v)
             }
-            _ => Err(ReadError::SchemaMismatch),
+            other => Err(ReadError::schema_mismatch::<bool>(other.type_name())),
         }
     }
     fn read_next_infallible(&mut self) -> Self::Read {
         self.next().unwrap_or_default()
     }
+    fn remaining(&self) -> Option<usize> {
+        Some(self.len())
+    }
 }
\ No newline at end of file
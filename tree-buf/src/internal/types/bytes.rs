@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::vec::IntoIter;
 
 #[cfg(feature = "read")]
 pub fn read_bytes<'a>(len: usize, bytes: &'a [u8], offset: &'_ mut usize) -> ReadResult<&'a [u8]> {
@@ -10,3 +11,134 @@ pub fn read_bytes<'a>(len: usize, bytes: &'a [u8], offset: &'_ mut usize) -> Rea
     *offset = end;
     Ok(&bytes[start..end])
 }
+
+// Byte slices are written in the same layout as Vec<u8>, except that
+// the values are always left uncompressed. This is what allows the reader
+// to hand out slices of the original bytes instead of copying.
+
+#[cfg(feature = "write")]
+fn write_raw_bytes<O: EncodeOptions>(value: &[u8], stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+    stream.write_with_len(|stream| stream.bytes.extend_from_slice(value));
+    ArrayTypeId::U8
+}
+
+#[cfg(feature = "write")]
+impl Writable for &[u8] {
+    type WriterArray = BytesArrayWriter;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        profile!("write_root");
+        match self.len() {
+            0 => RootTypeId::Array0,
+            // Not using Array1 for a single byte, since that would be
+            // written as an integer which can't be borrowed.
+            len => {
+                write_usize(len, stream);
+                stream.write_with_id(|stream| write_raw_bytes(self, stream));
+                RootTypeId::ArrayN
+            }
+        }
+    }
+}
+
+#[cfg(feature = "write")]
+#[derive(Default)]
+pub struct BytesArrayWriter {
+    len: Vec<u64>,
+    values: Vec<u8>,
+}
+
+#[cfg(feature = "write")]
+impl<'a> WriterArray<&'a [u8]> for BytesArrayWriter {
+    fn buffer<'b, 'c: 'b>(&'b mut self, value: &'c &'a [u8]) {
+        self.len.push(value.len() as u64);
+        self.values.extend_from_slice(value);
+    }
+    fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+        profile!("flush");
+        let Self { len, values } = self;
+        if let Some(first) = len.first() {
+            if len.iter().all(|l| l == first) {
+                write_usize(*first as usize, stream);
+                stream.write_with_id(|stream| write_raw_bytes(&values, stream));
+                return ArrayTypeId::ArrayFixed;
+            }
+        }
+        stream.write_with_id(|stream| len.flush(stream));
+        stream.write_with_id(|stream| write_raw_bytes(&values, stream));
+        ArrayTypeId::ArrayVar
+    }
+}
+
+/// Byte slices can only be borrowed if they were written without compression.
+#[cfg(feature = "read")]
+fn borrow_raw_bytes(sticks: DynArrayBranch<'_>) -> ReadResult<&'_ [u8]> {
+    match sticks {
+        DynArrayBranch::Integer(ArrayInteger {
+            bytes,
+            encoding: ArrayIntegerEncoding::U8,
+        }) => Ok(bytes.as_slice()),
+        DynArrayBranch::Void => Ok(&[]),
+        other => Err(ReadError::schema_mismatch::<&[u8]>(other.type_name())),
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a: 'b, 'b> Readable<'a> for &'b [u8] {
+    type ReaderArray = Option<BytesArrayReader<'b>>;
+    fn read(sticks: DynRootBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        match sticks {
+            DynRootBranch::Array0 => Ok(&[]),
+            DynRootBranch::Array { len, values } => {
+                let bytes = borrow_raw_bytes(values)?;
+                if bytes.len() != len {
+                    return Err(ReadError::InvalidFormat);
+                }
+                Ok(bytes)
+            }
+            other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
+        }
+    }
+}
+
+#[cfg(feature = "read")]
+pub struct BytesArrayReader<'a> {
+    len: FixedOrVariableLength,
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+#[cfg(feature = "read")]
+impl<'a: 'b, 'b> ReaderArray<'a> for Option<BytesArrayReader<'b>> {
+    type Read = &'b [u8];
+    type Error = ReadError;
+
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+
+        match sticks {
+            DynArrayBranch::Array0 => Ok(None),
+            DynArrayBranch::Array { len, values } => {
+                let len = FixedOrVariableLength::Variable(<IntoIter<u64> as ReaderArray>::new(*len, options)?);
+                let bytes = borrow_raw_bytes(*values)?;
+                Ok(Some(BytesArrayReader { len, bytes, offset: 0 }))
+            }
+            DynArrayBranch::ArrayFixed { len, values } => Ok(if len == 0 {
+                None
+            } else {
+                let len = FixedOrVariableLength::Fixed(len);
+                let bytes = borrow_raw_bytes(*values)?;
+                Some(BytesArrayReader { len, bytes, offset: 0 })
+            }),
+            other => Err(ReadError::schema_mismatch::<Self::Read>(other.type_name())),
+        }
+    }
+    fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
+        if let Some(inner) = self {
+            let len = inner.len.next();
+            read_bytes(len, inner.bytes, &mut inner.offset)
+        } else {
+            Ok(&[])
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::prelude::*;
+use std::fmt;
+#[cfg(feature = "read")]
+use std::vec::IntoIter;
+
+/// An exact decimal number, equal to `mantissa * 10^-scale`. Eg: 1.25 is a mantissa of 125 with a scale of 2.
+///
+/// Unlike floats, values such as prices are represented exactly. The mantissa and scale are written
+/// as separate columns, each compressed the same as other integers. Integers may be read as decimals.
+///
+/// Equality compares the mantissa and scale, so 1.0 and 1.00 are not equal.
+/// With the `rust_decimal` feature, `rust_decimal::Decimal` may be written and read directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Decimal {
+    pub mantissa: i128,
+    pub scale: u32,
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        Self { mantissa, scale }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.mantissa < 0 {
+            f.write_str("-")?;
+        }
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        if scale == 0 {
+            f.write_str(&digits)
+        } else if digits.len() > scale {
+            let (whole, fraction) = digits.split_at(digits.len() - scale);
+            write!(f, "{}.{}", whole, fraction)
+        } else {
+            write!(f, "0.{:0>width$}", digits, width = scale)
+        }
+    }
+}
+
+#[cfg(feature = "write")]
+impl Writable for Decimal {
+    type WriterArray = DecimalArrayWriter;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        profile!("write_root");
+        // Introduced in version 4 of the format. Before that, the mantissa and scale are written as a tuple
+        if stream.options.format_version() < 4 {
+            return (self.mantissa, self.scale).write_root(stream);
+        }
+        write_usize(self.scale as usize, stream);
+        stream.write_with_id(|stream| write_root_sint128(self.mantissa, stream));
+        RootTypeId::Decimal
+    }
+}
+
+#[cfg(feature = "write")]
+#[derive(Debug, Default)]
+pub struct DecimalArrayWriter {
+    mantissa: Vec<i128>,
+    scale: Vec<u32>,
+}
+
+#[cfg(feature = "write")]
+impl WriterArray<Decimal> for DecimalArrayWriter {
+    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b Decimal) {
+        self.mantissa.push(value.mantissa);
+        self.scale.push(value.scale);
+    }
+    fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+        profile!("WriterArray::flush");
+        let Self { mantissa, scale } = self;
+        if mantissa.is_empty() {
+            return ArrayTypeId::Void;
+        }
+        stream.write_with_id(|stream| mantissa.flush(stream));
+        stream.write_with_id(|stream| scale.flush(stream));
+        // The same children as a tuple. See also Decimal::write_root
+        if stream.options.format_version() < 4 {
+            ArrayTypeId::Tuple2
+        } else {
+            ArrayTypeId::Decimal
+        }
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> Readable<'a> for Decimal {
+    type ReaderArray = DecimalArrayReader;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        match sticks {
+            DynRootBranch::Decimal { mantissa, scale } => Ok(Decimal::new(i128::read(*mantissa, options)?, scale)),
+            DynRootBranch::Integer(_) => Ok(Decimal::new(i128::read(sticks, options)?, 0)),
+            // See also Decimal::write_root
+            DynRootBranch::Tuple { ref fields } if fields.len() == 2 => {
+                let (mantissa, scale) = <(i128, u32)>::read(sticks, options).map_err(|_| ReadError::schema_mismatch::<Self>("Tuple"))?;
+                Ok(Decimal::new(mantissa, scale))
+            }
+            other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
+        }
+    }
+}
+
+#[cfg(feature = "read")]
+pub struct DecimalArrayReader {
+    mantissa: IntoIter<i128>,
+    // None when reading integers, which have a scale of 0
+    scale: Option<IntoIter<u32>>,
+}
+
+#[cfg(feature = "read")]
+impl<'a> InfallibleReaderArray<'a> for DecimalArrayReader {
+    type Read = Decimal;
+
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+        let (mantissa, scale) = match sticks {
+            DynArrayBranch::Decimal { mantissa, scale } => (*mantissa, *scale),
+            // See also WriterArray::flush for DecimalArrayWriter
+            DynArrayBranch::Tuple { fields } if fields.len() == 2 => {
+                let mut fields = fields.into_iter();
+                (fields.next().unwrap(), fields.next().unwrap())
+            }
+            other => {
+                let found = other.type_name();
+                let mantissa = InfallibleReaderArray::new_infallible(other, options).map_err(|e| match e.kind() {
+                    ReadError::SchemaMismatch => ReadError::schema_mismatch::<Decimal>(found),
+                    _ => e,
+                })?;
+                return Ok(DecimalArrayReader { mantissa, scale: None });
+            }
+        };
+        Ok(DecimalArrayReader {
+            mantissa: InfallibleReaderArray::new_infallible(mantissa, options)?,
+            scale: Some(InfallibleReaderArray::new_infallible(scale, options)?),
+        })
+    }
+    fn read_next_infallible(&mut self) -> Self::Read {
+        let mantissa = self.mantissa.read_next_infallible();
+        let scale = self.scale.as_mut().map_or(0, |scale| scale.read_next_infallible());
+        Decimal::new(mantissa, scale)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for Decimal {
+    #[inline]
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Decimal::new(value.mantissa(), value.scale())
+    }
+}
+
+/// Fails for decimals which do not fit, which have more than 96 bits of mantissa or a scale greater than 28.
+#[cfg(all(feature = "read", feature = "rust_decimal"))]
+fn to_rust_decimal(value: Decimal) -> ReadResult<rust_decimal::Decimal> {
+    rust_decimal::Decimal::try_from_i128_with_scale(value.mantissa, value.scale).map_err(|_| ReadError::schema_mismatch::<rust_decimal::Decimal>("Decimal"))
+}
+
+#[cfg(all(feature = "write", feature = "rust_decimal"))]
+impl Writable for rust_decimal::Decimal {
+    type WriterArray = DecimalArrayWriter;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        Decimal::from(*self).write_root(stream)
+    }
+}
+
+#[cfg(all(feature = "write", feature = "rust_decimal"))]
+impl WriterArray<rust_decimal::Decimal> for DecimalArrayWriter {
+    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b rust_decimal::Decimal) {
+        self.mantissa.push(value.mantissa());
+        self.scale.push(value.scale());
+    }
+    fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+        <Self as WriterArray<Decimal>>::flush(self, stream)
+    }
+}
+
+#[cfg(all(feature = "read", feature = "rust_decimal"))]
+impl<'a> Readable<'a> for rust_decimal::Decimal {
+    type ReaderArray = RustDecimalArrayReader;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        to_rust_decimal(Decimal::read(sticks, options)?)
+    }
+}
+
+#[cfg(all(feature = "read", feature = "rust_decimal"))]
+pub struct RustDecimalArrayReader(DecimalArrayReader);
+
+#[cfg(all(feature = "read", feature = "rust_decimal"))]
+impl<'a> ReaderArray<'a> for RustDecimalArrayReader {
+    type Read = rust_decimal::Decimal;
+    type Error = ReadError;
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+        Ok(RustDecimalArrayReader(InfallibleReaderArray::new_infallible(sticks, options)?))
+    }
+    fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
+        to_rust_decimal(self.0.read_next_infallible())
+    }
+}
@@ -1,4 +1,5 @@
 //use crate::encodings::zfp;
+use crate::internal::encodings::byte_stream_split;
 use crate::prelude::*;
 use num_traits::AsPrimitive as _;
 use std::convert::TryInto;
@@ -29,7 +30,7 @@ use flame;
 // TODO: More compressors
 
 macro_rules! impl_float {
-    ($T:ident, $write_item:ident, $read_item:ident, $id:ident, $fixed:ident, $Gorilla:ident, $Zfp:ident, $($rest:ident),*) => {
+    ($T:ident, $Bits:ident, $write_item:ident, $read_item:ident, $id:ident, $fixed:ident, $Gorilla:ident, $Zfp:ident, $split_id:ident, $ByteStreamSplit:ident, $($rest:ident),*) => {
         // TODO: Check for lowering - f64 -> f63
         #[cfg(feature = "write")]
         fn $write_item(item: $T, bytes: &mut Vec<u8>) {
@@ -72,9 +73,9 @@ macro_rules! impl_float {
 
 
         #[cfg(feature = "read")]
-        impl Readable for $T {
+        impl<'a> Readable<'a> for $T {
             type ReaderArray = IntoIter<$T>;
-            fn read(sticks: DynRootBranch<'_>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+            fn read(sticks: DynRootBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
                 profile!("Readable::read");
                 match sticks {
                     DynRootBranch::Integer(root_integer) => {
@@ -85,7 +86,7 @@ macro_rules! impl_float {
                                 if u < (2 << std::$T::MANTISSA_DIGITS) {
                                     Ok(u as $T)
                                 } else {
-                                    Err(ReadError::SchemaMismatch)
+                                    Err(ReadError::schema_mismatch::<Self>("Integer"))
                                 }
                             }
                             RootInteger::S(s) => {
@@ -93,9 +94,11 @@ macro_rules! impl_float {
                                     // FIXME: Made up number
                                     Ok(s as $T)
                                 } else {
-                                    Err(ReadError::SchemaMismatch)
+                                    Err(ReadError::schema_mismatch::<Self>("Integer"))
                                 }
                             }
+                            // These don't fit in 64 bits, so can't be represented exactly
+                            RootInteger::U128(_) | RootInteger::S128(_) => Err(ReadError::schema_mismatch::<Self>("Integer")),
                         }
                     }
                     DynRootBranch::Float(root_float) => {
@@ -110,16 +113,24 @@ macro_rules! impl_float {
                             RootFloat::F32(v) => Ok(v as $T),
                         }
                     }
-                    _ => Err(ReadError::SchemaMismatch),
+                    other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
                 }
             }
         }
 
 
+        #[cfg(feature = "write")]
+        impl BitEq for $T {
+            type Bits = $Bits;
+            fn bits(&self) -> Self::Bits {
+                self.to_bits()
+            }
+        }
+
         #[cfg(feature = "read")]
-        impl InfallibleReaderArray for IntoIter<$T> {
+        impl<'a> InfallibleReaderArray<'a> for IntoIter<$T> {
             type Read = $T;
-            fn new_infallible(sticks: DynArrayBranch<'_>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+            fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
                 profile!("ReaderArray::new");
 
                 match sticks {
@@ -143,6 +154,16 @@ macro_rules! impl_float {
                             ArrayFloat::DoubleGorilla(bytes) => {
                                 gorilla::decompress::<$T>(&bytes).map(|f| f.into_iter())
                             },
+                            ArrayFloat::ByteStreamSplit64(bytes) => {
+                                let bytes = byte_stream_split::decompress(&bytes, size_of::<f64>(), &Limits::new(options))?;
+                                let values = read_all(&bytes, |bytes, offset| Ok(read_64(bytes, offset)?.as_()))?;
+                                Ok(values.into_iter())
+                            }
+                            ArrayFloat::ByteStreamSplit32(bytes) => {
+                                let bytes = byte_stream_split::decompress(&bytes, size_of::<f32>(), &Limits::new(options))?;
+                                let values = read_all(&bytes, |bytes, offset| Ok(read_32(bytes, offset)?.as_()))?;
+                                Ok(values.into_iter())
+                            }
                             /*
                             ArrayFloat::Zfp32(bytes) => {
                                 // FIXME: This is likely a bug switching between 32 and 64 might just get garbage data out
@@ -158,22 +179,31 @@ macro_rules! impl_float {
                                 Ok(values.into_iter())
                             }
                             */
-                            ArrayFloat::Zfp32(_bytes) => {
-                                unimplemented!("zfp32")
-                            }
-                            ArrayFloat::Zfp64(_bytes) => {
-                                unimplemented!("zfp64")
-                            }
+                            // Zfp is not written, so this can only come from a corrupt document.
+                            ArrayFloat::Zfp32(_) | ArrayFloat::Zfp64(_) => Err(ReadError::InvalidFormat),
                         }
                     }
+                    DynArrayBranch::RLE { runs, values } => {
+                        let rle = RleIterator::new(runs, values, options, |values| Self::new_infallible(values, options))?;
+                        let all = rle.collect::<Vec<_>>();
+                        Ok(all.into_iter())
+                    }
+                    DynArrayBranch::Dictionary { indices, values } => {
+                        let dict = DictionaryIterator::new(indices, values, options, |values| Self::new_infallible(values, options))?;
+                        let all = dict.collect::<Vec<_>>();
+                        Ok(all.into_iter())
+                    }
                     // TODO: There are some conversions that are infallable.
                     // Eg: Simple16.
-                    _ => Err(ReadError::SchemaMismatch),
+                    other => Err(ReadError::schema_mismatch::<$T>(other.type_name())),
                 }
             }
             fn read_next_infallible(&mut self) -> Self::Read {
                 self.next().unwrap_or_default()
             }
+            fn remaining(&self) -> Option<usize> {
+                Some(self.len())
+            }
         }
 
         #[cfg(feature = "write")]
@@ -192,6 +222,19 @@ macro_rules! impl_float {
                     $($rest,)*
                 );
 
+                // Introduced in version 8 of the format
+                if stream.options.format_version() < 8 {
+                    return compress(&self, stream, &compressors);
+                }
+
+                let compressors = (
+                    $fixed,
+                    $Gorilla { tolerance },
+                    $ByteStreamSplit,
+                    RLE::new(($fixed, $Gorilla { tolerance }, $ByteStreamSplit)),
+                    Dictionary::new(($fixed, $Gorilla { tolerance }, $ByteStreamSplit)),
+                );
+
                 compress(&self, stream, &compressors)
             }
         }
@@ -212,6 +255,24 @@ macro_rules! impl_float {
             }
         }
 
+        // Lossless, even when there is a tolerance
+        struct $ByteStreamSplit;
+        impl Compressor<$T> for $ByteStreamSplit {
+            fn compress<O: EncodeOptions>(&self, data: &[$T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
+                profile!("compress");
+                // Introduced in version 9 of the format
+                if stream.options.format_version() < 9 {
+                    return Err(());
+                }
+                let mut bytes = Vec::with_capacity(size_of::<$T>() * data.len());
+                for item in data {
+                    $write_item(*item, &mut bytes);
+                }
+                stream.write_with_len(|stream| byte_stream_split::compress(&bytes, size_of::<$T>(), stream.bytes));
+                Ok(ArrayTypeId::$split_id)
+            }
+        }
+
         // FIXME: Not clear if this is canon. The source for gibbon is a bit shaky.
         // Alternatively, there is the tsz crate, but that doesn't offer a separate
         // double-stream (just joined time+double stream). Both of the implementations
@@ -223,8 +284,11 @@ macro_rules! impl_float {
             fn compress<O: EncodeOptions>(&self, data: &[$T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
                 profile!("compress");
 
+                if data.is_empty() {
+                    return Err(());
+                }
                 stream.write_with_len(|stream| {
-                    if let Some(tolerance) = self.tolerance {
+                    Ok(if let Some(tolerance) = self.tolerance {
                         // TODO: This is a hack (albeit a surprisingly effective one) to get lossy compression
                         // before a real lossy compressor (Eg: fzip) is used.
                         let multiplier = (2.0 as $T).powi(tolerance * -1);
@@ -233,7 +297,7 @@ macro_rules! impl_float {
                     } else {
                         let data = data.iter().map(|f| *f as f64);
                         gorilla::compress(data, stream.bytes)
-                    }
+                    })
                 })
 
             }
@@ -263,5 +327,27 @@ impl Compressor<f32> for Zfp32 {
 }
 */
 
-impl_float!(f64, write_64, read_64, F64, Fixed64Compressor, GorillaCompressor64, Zfp64,);
-impl_float!(f32, write_32, read_32, F32, Fixed32Compressor, GorillaCompressor32, Zfp32,);
+impl_float!(
+    f64,
+    u64,
+    write_64,
+    read_64,
+    F64,
+    Fixed64Compressor,
+    GorillaCompressor64,
+    Zfp64,
+    ByteStreamSplit64,
+    ByteStreamSplit64Compressor,
+);
+impl_float!(
+    f32,
+    u32,
+    write_32,
+    read_32,
+    F32,
+    Fixed32Compressor,
+    GorillaCompressor32,
+    Zfp32,
+    ByteStreamSplit32,
+    ByteStreamSplit32Compressor,
+);
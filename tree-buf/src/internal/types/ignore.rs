@@ -12,9 +12,9 @@ impl Writable for Ignore {
 }
 
 #[cfg(feature = "read")]
-impl Readable for Ignore {
+impl<'a> Readable<'a> for Ignore {
     type ReaderArray = Ignore;
-    fn read(_sticks: DynRootBranch<'_>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn read(_sticks: DynRootBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
         Ok(Self)
     }
 }
@@ -28,9 +28,9 @@ impl WriterArray<Ignore> for Ignore {
 }
 
 #[cfg(feature = "read")]
-impl InfallibleReaderArray for Ignore {
+impl<'a> InfallibleReaderArray<'a> for Ignore {
     type Read = Ignore;
-    fn new_infallible(_sticks: DynArrayBranch<'_>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn new_infallible(_sticks: DynArrayBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
         Ok(Ignore)
     }
     fn read_next_infallible(&mut self) -> Self::Read {
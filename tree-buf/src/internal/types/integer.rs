@@ -1,4 +1,10 @@
+#[cfg(feature = "read")]
+use crate::internal::encodings::bitpack::{decode_bit_packed, decode_frame_of_reference, decode_patched_frame_of_reference};
 use crate::internal::encodings::compress;
+#[cfg(feature = "read")]
+use crate::internal::encodings::delta::delta_decode_in_place;
+#[cfg(feature = "read")]
+use crate::internal::encodings::simple_8b::decompress as decompress_simple_8b;
 use crate::internal::encodings::varint::*;
 use crate::prelude::*;
 use num_traits::{AsPrimitive, Bounded};
@@ -7,6 +13,7 @@ use std::any::TypeId;
 use std::convert::{TryFrom, TryInto};
 use std::mem::transmute;
 use std::vec::IntoIter;
+use zigzag::ZigZag;
 
 #[derive(Copy, Clone)]
 struct U0;
@@ -52,6 +59,14 @@ macro_rules! impl_lowerable {
             }
         }
 
+        #[cfg(feature = "write")]
+        impl BitEq for $Ty {
+            type Bits = $Ty;
+            fn bits(&self) -> Self::Bits {
+                *self
+            }
+        }
+
         #[cfg(feature = "write")]
         impl WriterArray<$Ty> for Vec<$Ty> {
             fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b $Ty) {
@@ -78,30 +93,31 @@ macro_rules! impl_lowerable {
         }
 
         #[cfg(feature = "read")]
-        impl Readable for $Ty {
+        impl<'a> Readable<'a> for $Ty {
             type ReaderArray = IntoIter<$Ty>;
-            fn read(sticks: DynRootBranch<'_>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+            fn read(sticks: DynRootBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
                 profile!("Readable::read");
                 match sticks {
                     DynRootBranch::Integer(root_int) => {
                         match root_int {
-                            RootInteger::U(v) => v.try_into().map_err(|_| ReadError::SchemaMismatch),
-                            _ => Err(ReadError::SchemaMismatch),
+                            RootInteger::U(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                            RootInteger::S(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                            RootInteger::U128(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                            RootInteger::S128(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
                         }
                     }
-                    _ => Err(ReadError::SchemaMismatch),
+                    other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
                 }
             }
         }
 
         #[cfg(feature = "read")]
-        impl InfallibleReaderArray for IntoIter<$Ty> {
+        impl<'a> InfallibleReaderArray<'a> for IntoIter<$Ty> {
             type Read = $Ty;
-            fn new_infallible(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+            fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
                 profile!(Self::Read, "ReaderArray::new");
 
                 match sticks {
-                    // TODO: Support eg: delta/zigzag
                     DynArrayBranch::Integer(array_int) => {
                         let ArrayInteger { bytes, encoding } = array_int;
                         match encoding {
@@ -112,7 +128,7 @@ macro_rules! impl_lowerable {
                                 let v: Vec<$Ty> = read_all(
                                         &bytes,
                                         |bytes, offset| {
-                                            let r: $Ty = decode_prefix_varint(bytes, offset)?.try_into().map_err(|_| ReadError::SchemaMismatch)?;
+                                            let r: $Ty = decode_prefix_varint(bytes, offset)?.try_into().map_err(|_| ReadError::schema_mismatch::<$Ty>("Integer"))?;
                                             Ok(r)
                                         }
                                 )?;
@@ -125,7 +141,7 @@ macro_rules! impl_lowerable {
                                 let mut v = Vec::new();
                                 simple_16::decompress(&bytes, &mut v).map_err(|_| ReadError::InvalidFormat)?;
                                 let result: Result<Vec<_>, _> = v.into_iter().map(TryInto::<$Ty>::try_into).collect();
-                                let v = result.map_err(|_| ReadError::SchemaMismatch)?;
+                                let v = result.map_err(|_| ReadError::schema_mismatch::<$Ty>("Integer"))?;
                                 Ok(v.into_iter())
                             },
                             ArrayIntegerEncoding::U8 => {
@@ -135,7 +151,25 @@ macro_rules! impl_lowerable {
                                 let v: Vec<$Ty> = bytes.iter().map(|&b| b.into()).collect();
                                 Ok(v.into_iter())
                             }
+                            ArrayIntegerEncoding::BitPacked => narrow(decode_bit_packed(&bytes, &Limits::new(options))?),
+                            ArrayIntegerEncoding::FrameOfReference => narrow(decode_frame_of_reference(&bytes, &Limits::new(options))?),
+                            ArrayIntegerEncoding::PatchedFrameOfReference => narrow(decode_patched_frame_of_reference(&bytes, &Limits::new(options))?),
+                            ArrayIntegerEncoding::Simple8b => narrow(decompress_simple_8b(&bytes, &Limits::new(options))?),
+                        }
+                    },
+                    DynArrayBranch::Delta { order, len, values } => {
+                        // Deltas may be outside the range of the type, even when the values are not.
+                        let mut values = <IntoIter<u64> as InfallibleReaderArray>::new_infallible(*values, options)?.collect::<Vec<_>>();
+                        if values.len() < len {
+                            return Err(ReadError::InvalidFormat);
+                        }
+                        values.truncate(len);
+                        for _ in 0..order {
+                            delta_decode_in_place(&mut values);
                         }
+                        let result: Result<Vec<_>, _> = values.into_iter().map(TryInto::<$Ty>::try_into).collect();
+                        let v = result.map_err(|_| ReadError::schema_mismatch::<$Ty>("Integer"))?;
+                        Ok(v.into_iter())
                     },
                     DynArrayBranch::RLE { runs, values } => {
                         let rle = RleIterator::new(runs, values, options, |values| Self::new_infallible(values, options))?;
@@ -149,6 +183,8 @@ macro_rules! impl_lowerable {
                     DynArrayBranch::Void => {
                         Ok(Vec::new().into_iter())
                     }
+                    // Negative values can't be read as an unsigned type
+                    DynArrayBranch::Zigzag { .. } => Err(ReadError::schema_mismatch::<$Ty>("Zigzag")),
                     other => {
                         let bools = <IntoIter<bool> as InfallibleReaderArray>::new_infallible(other, options)?;
                         let mapped = bools.map(|i| if i {1} else {0}).collect::<Vec<_>>();
@@ -159,6 +195,9 @@ macro_rules! impl_lowerable {
             fn read_next_infallible(&mut self) -> Self::Read {
                 self.next().unwrap_or_default()
             }
+            fn remaining(&self) -> Option<usize> {
+                Some(self.len())
+            }
         }
 
         #[cfg(feature = "write")]
@@ -209,13 +248,76 @@ macro_rules! impl_lowerable {
 // Broadly we only want to downcast if it allows for some other kind of compressor to be used.
 
 // Type, array writer, next lower, next lower writer, non-inferred lowers
-impl_lowerable!(u64, write_u64, u32, write_u32, (u16), (PrefixVarIntCompressor));
-impl_lowerable!(u32, write_u32, u16, write_u16, (), (Simple16Compressor, PrefixVarIntCompressor)); // TODO: Consider replacing PrefixVarInt at this level with Fixed.
-impl_lowerable!(u16, write_u16, u8, write_u8, (), (Simple16Compressor, PrefixVarIntCompressor));
+impl_lowerable!(
+    u64,
+    write_u64,
+    u32,
+    write_u32,
+    (u16),
+    (
+        Simple8bCompressor,
+        PrefixVarIntCompressor,
+        Delta,
+        DeltaOfDelta,
+        BitPackedCompressor,
+        FrameOfReferenceCompressor,
+        PatchedFrameOfReferenceCompressor
+    )
+);
+impl_lowerable!(
+    u32,
+    write_u32,
+    u16,
+    write_u16,
+    (),
+    (
+        Simple16Compressor,
+        PrefixVarIntCompressor, // TODO: Consider replacing PrefixVarInt at this level with Fixed.
+        Delta,
+        DeltaOfDelta,
+        BitPackedCompressor,
+        FrameOfReferenceCompressor,
+        PatchedFrameOfReferenceCompressor
+    )
+);
+impl_lowerable!(
+    u16,
+    write_u16,
+    u8,
+    write_u8,
+    (),
+    (
+        Simple16Compressor,
+        PrefixVarIntCompressor,
+        Delta,
+        DeltaOfDelta,
+        BitPackedCompressor,
+        FrameOfReferenceCompressor,
+        PatchedFrameOfReferenceCompressor
+    )
+);
 impl_lowerable!(u8, write_u8, U0, write_u0, (), (Simple16Compressor, BytesCompressor));
 
+/// Checks that integers decoded as u64 fit in the type being read.
+#[cfg(feature = "read")]
+fn narrow<T: TryFrom<u64>>(values: Vec<u64>) -> ReadResult<IntoIter<T>> {
+    let result: Result<Vec<T>, _> = values.into_iter().map(TryInto::try_into).collect();
+    let v = result.map_err(|_| ReadError::schema_mismatch::<T>("Integer"))?;
+    Ok(v.into_iter())
+}
+
+/// Writes the integers without lowering them to bools, so that
+/// they can be distinguished from bools by a schema-less reader.
 #[cfg(feature = "write")]
-fn write_root_uint(value: u64, bytes: &mut Vec<u8>) -> RootTypeId {
+pub(crate) fn write_uints<O: EncodeOptions>(data: &[u64], stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+    match data.iter().max() {
+        Some(max) => write_u64(data, *max, stream),
+        None => ArrayTypeId::Void,
+    }
+}
+
+#[cfg(feature = "write")]
+pub(crate) fn write_root_uint(value: u64, bytes: &mut Vec<u8>) -> RootTypeId {
     let le = value.to_le_bytes();
     match value {
         0 => RootTypeId::Zero,
@@ -244,7 +346,7 @@ fn write_root_uint(value: u64, bytes: &mut Vec<u8>) -> RootTypeId {
             bytes.extend_from_slice(&le[..6]);
             RootTypeId::IntU48
         }
-        281474976710656..=72057594037927936 => {
+        281474976710656..=72057594037927935 => {
             bytes.extend_from_slice(&le[..7]);
             RootTypeId::IntU56
         }
@@ -255,6 +357,358 @@ fn write_root_uint(value: u64, bytes: &mut Vec<u8>) -> RootTypeId {
     }
 }
 
+// Signed integers are stored as their unsigned counterparts whenever there are no
+// negative values, which keeps the format interchangeable between eg: i32 and u32
+// for the common case. Otherwise the values are zigzag encoded and nested inside
+// a Zigzag branch so that small magnitudes still compress well.
+macro_rules! impl_signed {
+    ($Ty:ty, $Uty:ty) => {
+        #[cfg(feature = "write")]
+        impl Writable for $Ty {
+            type WriterArray = Vec<$Ty>;
+            fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+                let value = *self as i64;
+                if value >= 0 {
+                    write_root_uint(value as u64, stream.bytes)
+                } else {
+                    write_root_sint(value, stream.bytes)
+                }
+            }
+        }
+
+        #[cfg(feature = "write")]
+        impl WriterArray<$Ty> for Vec<$Ty> {
+            fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b $Ty) {
+                self.push(*value);
+            }
+            fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+                profile!("WriterArray::flush");
+                // TODO: (Performance) Use second-stack
+                if self.iter().all(|v| *v >= 0) {
+                    let unsigned = self.iter().map(|v| *v as $Uty).collect::<Vec<_>>();
+                    unsigned.flush(stream)
+                } else {
+                    let zigzag = self.iter().map(|v| ZigZag::encode(*v)).collect::<Vec<$Uty>>();
+                    stream.write_with_id(|stream| zigzag.flush(stream));
+                    ArrayTypeId::Zigzag
+                }
+            }
+        }
+
+        #[cfg(feature = "read")]
+        impl<'a> Readable<'a> for $Ty {
+            type ReaderArray = IntoIter<$Ty>;
+            fn read(sticks: DynRootBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!("Readable::read");
+                match sticks {
+                    DynRootBranch::Integer(root_int) => match root_int {
+                        RootInteger::U(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                        RootInteger::S(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                        RootInteger::U128(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                        RootInteger::S128(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                    },
+                    other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
+                }
+            }
+        }
+
+        #[cfg(feature = "read")]
+        impl<'a> InfallibleReaderArray<'a> for IntoIter<$Ty> {
+            type Read = $Ty;
+            fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!(Self::Read, "ReaderArray::new");
+
+                // Zigzag is independent of width, so everything is decoded through 64 bits
+                // and then checked against the range of the requested type.
+                let values: Result<Vec<$Ty>, ()> = match sticks {
+                    DynArrayBranch::Zigzag { values } => {
+                        let values = <IntoIter<u64> as InfallibleReaderArray>::new_infallible(*values, options)?;
+                        values.map(|v| <i64 as ZigZag>::decode(v).try_into().map_err(|_| ())).collect()
+                    }
+                    other => {
+                        let found = other.type_name();
+                        let values = <IntoIter<u64> as InfallibleReaderArray>::new_infallible(other, options).map_err(|e| match e.kind() {
+                            ReadError::SchemaMismatch => ReadError::schema_mismatch::<$Ty>(found),
+                            _ => e,
+                        })?;
+                        values.map(|v| v.try_into().map_err(|_| ())).collect()
+                    }
+                };
+                let values = values.map_err(|_| ReadError::schema_mismatch::<$Ty>("Integer"))?;
+                Ok(values.into_iter())
+            }
+            fn read_next_infallible(&mut self) -> Self::Read {
+                self.next().unwrap_or_default()
+            }
+            fn remaining(&self) -> Option<usize> {
+                Some(self.len())
+            }
+        }
+    };
+}
+
+impl_signed!(i64, u64);
+impl_signed!(i32, u32);
+impl_signed!(i16, u16);
+impl_signed!(i8, u8);
+
+/// Writes a negative integer using the fewest bytes which preserve the value
+/// once sign extended.
+#[cfg(feature = "write")]
+pub(crate) fn write_root_sint(value: i64, bytes: &mut Vec<u8>) -> RootTypeId {
+    let le = value.to_le_bytes();
+    match value {
+        -1 => RootTypeId::NegOne,
+        -128..=-2 => {
+            bytes.push(le[0]);
+            RootTypeId::IntS8
+        }
+        -32768..=-129 => {
+            bytes.extend_from_slice(&le[..2]);
+            RootTypeId::IntS16
+        }
+        -8388608..=-32769 => {
+            bytes.extend_from_slice(&le[..3]);
+            RootTypeId::IntS24
+        }
+        -2147483648..=-8388609 => {
+            bytes.extend_from_slice(&le[..4]);
+            RootTypeId::IntS32
+        }
+        -549755813888..=-2147483649 => {
+            bytes.extend_from_slice(&le[..5]);
+            RootTypeId::IntS40
+        }
+        -140737488355328..=-549755813889 => {
+            bytes.extend_from_slice(&le[..6]);
+            RootTypeId::IntS48
+        }
+        -36028797018963968..=-140737488355329 => {
+            bytes.extend_from_slice(&le[..7]);
+            RootTypeId::IntS56
+        }
+        _ => {
+            bytes.extend_from_slice(&le);
+            RootTypeId::IntS64
+        }
+    }
+}
+
+/// Writes an integer which may not fit in 64 bits. Values that do fit
+/// are written the same as u64, so that they may be read as any integer type.
+///
+/// Version 1 of the format has no 128 bit integers, so for that
+/// the high and low 64 bits are written as a tuple instead.
+#[cfg(feature = "write")]
+pub(crate) fn write_root_uint128<O: EncodeOptions>(value: u128, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+    match u64::try_from(value) {
+        Ok(value) => write_root_uint(value, stream.bytes),
+        Err(_) if stream.options.format_version() < 2 => ((value >> 64) as u64, value as u64).write_root(stream),
+        Err(_) => {
+            stream.bytes.extend_from_slice(&value.to_le_bytes());
+            RootTypeId::IntU128
+        }
+    }
+}
+
+#[cfg(feature = "write")]
+pub(crate) fn write_root_sint128<O: EncodeOptions>(value: i128, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+    if value >= 0 {
+        write_root_uint128(value as u128, stream)
+    } else if let Ok(value) = i64::try_from(value) {
+        write_root_sint(value, stream.bytes)
+    } else if stream.options.format_version() < 2 {
+        ((value >> 64) as i64, value as u64).write_root(stream)
+    } else {
+        stream.bytes.extend_from_slice(&value.to_le_bytes());
+        RootTypeId::IntS128
+    }
+}
+
+/// Writes integers which may not fit in 64 bits. If they all fit, this is the same as u64.
+/// Otherwise the high and low 64 bits are written as separate arrays so that
+/// the existing compressors apply to each.
+#[cfg(feature = "write")]
+pub(crate) fn write_uints128<O: EncodeOptions>(data: &[u128], stream: &mut WriterStream<'_, O>, write: impl Fn(&[u64], &mut WriterStream<'_, O>) -> ArrayTypeId) -> ArrayTypeId {
+    if let Ok(narrow) = data.iter().map(|v| u64::try_from(*v)).collect::<Result<Vec<_>, _>>() {
+        return write(&narrow, stream);
+    }
+    let hi = data.iter().map(|v| (*v >> 64) as u64).collect::<Vec<_>>();
+    let lo = data.iter().map(|v| *v as u64).collect::<Vec<_>>();
+    stream.write_with_id(|stream| write(&hi, stream));
+    stream.write_with_id(|stream| write(&lo, stream));
+    // The same children as a tuple, for version 1 of the format. See also write_root_uint128
+    if stream.options.format_version() < 2 {
+        ArrayTypeId::Tuple2
+    } else {
+        ArrayTypeId::U128
+    }
+}
+
+#[cfg(feature = "write")]
+impl Writable for u128 {
+    type WriterArray = Vec<u128>;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        write_root_uint128(*self, stream)
+    }
+}
+
+#[cfg(feature = "write")]
+impl WriterArray<u128> for Vec<u128> {
+    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b u128) {
+        self.push(*value);
+    }
+    fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+        profile!("WriterArray::flush");
+        write_uints128(&self, stream, |data, stream| data.to_vec().flush(stream))
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> Readable<'a> for u128 {
+    type ReaderArray = IntoIter<u128>;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        match sticks {
+            DynRootBranch::Integer(root_int) => match root_int {
+                RootInteger::U(v) => Ok(v.into()),
+                RootInteger::S(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                RootInteger::U128(v) => Ok(v),
+                RootInteger::S128(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+            },
+            // See also write_root_uint128
+            DynRootBranch::Tuple { ref fields } if fields.len() == 2 => {
+                let (hi, lo) = <(u64, u64)>::read(sticks, options).map_err(|_| ReadError::schema_mismatch::<Self>("Tuple"))?;
+                Ok((u128::from(hi) << 64) | u128::from(lo))
+            }
+            other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
+        }
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> InfallibleReaderArray<'a> for IntoIter<u128> {
+    type Read = u128;
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!(Self::Read, "ReaderArray::new");
+        let (hi, lo) = match sticks {
+            DynArrayBranch::U128 { hi, lo } => (*hi, *lo),
+            // See also write_uints128
+            DynArrayBranch::Tuple { fields } if fields.len() == 2 => {
+                let mut fields = fields.into_iter();
+                (fields.next().unwrap(), fields.next().unwrap())
+            }
+            other => {
+                let found = other.type_name();
+                let values = <IntoIter<u64> as InfallibleReaderArray>::new_infallible(other, options).map_err(|e| match e.kind() {
+                    ReadError::SchemaMismatch => ReadError::schema_mismatch::<u128>(found),
+                    _ => e,
+                })?;
+                return Ok(values.map(u128::from).collect::<Vec<_>>().into_iter());
+            }
+        };
+        let (hi, lo) = parallel(
+            || <IntoIter<u64> as InfallibleReaderArray>::new_infallible(hi, options),
+            || <IntoIter<u64> as InfallibleReaderArray>::new_infallible(lo, options),
+            options,
+        );
+        let (mut hi, mut lo) = (hi?, lo?);
+        let len = hi.len().max(lo.len());
+        let values = (0..len)
+            .map(|_| (u128::from(hi.next().unwrap_or_default()) << 64) | u128::from(lo.next().unwrap_or_default()))
+            .collect::<Vec<_>>();
+        Ok(values.into_iter())
+    }
+    fn read_next_infallible(&mut self) -> Self::Read {
+        self.next().unwrap_or_default()
+    }
+    fn remaining(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+// See also impl_signed
+#[cfg(feature = "write")]
+impl Writable for i128 {
+    type WriterArray = Vec<i128>;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        write_root_sint128(*self, stream)
+    }
+}
+
+#[cfg(feature = "write")]
+impl WriterArray<i128> for Vec<i128> {
+    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b i128) {
+        self.push(*value);
+    }
+    fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+        profile!("WriterArray::flush");
+        // TODO: (Performance) Use second-stack
+        if self.iter().all(|v| *v >= 0) {
+            let unsigned = self.iter().map(|v| *v as u128).collect::<Vec<_>>();
+            unsigned.flush(stream)
+        } else {
+            // Zigzag is the same for i64 and i128 in the range of i64,
+            // so these can be read as i64 when the values fit.
+            let zigzag = self.iter().map(|v| ZigZag::encode(*v)).collect::<Vec<u128>>();
+            stream.write_with_id(|stream| zigzag.flush(stream));
+            ArrayTypeId::Zigzag
+        }
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> Readable<'a> for i128 {
+    type ReaderArray = IntoIter<i128>;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        match sticks {
+            DynRootBranch::Integer(root_int) => match root_int {
+                RootInteger::U(v) => Ok(v.into()),
+                RootInteger::S(v) => Ok(v.into()),
+                RootInteger::U128(v) => v.try_into().map_err(|_| ReadError::schema_mismatch::<Self>("Integer")),
+                RootInteger::S128(v) => Ok(v),
+            },
+            // See also write_root_sint128
+            DynRootBranch::Tuple { ref fields } if fields.len() == 2 => {
+                let (hi, lo) = <(i64, u64)>::read(sticks, options).map_err(|_| ReadError::schema_mismatch::<Self>("Tuple"))?;
+                Ok((i128::from(hi) << 64) | i128::from(lo))
+            }
+            other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
+        }
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> InfallibleReaderArray<'a> for IntoIter<i128> {
+    type Read = i128;
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!(Self::Read, "ReaderArray::new");
+        let values: Result<Vec<i128>, ()> = match sticks {
+            DynArrayBranch::Zigzag { values } => {
+                let values = <IntoIter<u128> as InfallibleReaderArray>::new_infallible(*values, options)?;
+                Ok(values.map(<i128 as ZigZag>::decode).collect())
+            }
+            other => {
+                let found = other.type_name();
+                let values = <IntoIter<u128> as InfallibleReaderArray>::new_infallible(other, options).map_err(|e| match e.kind() {
+                    ReadError::SchemaMismatch => ReadError::schema_mismatch::<i128>(found),
+                    _ => e,
+                })?;
+                values.map(|v| v.try_into().map_err(|_| ())).collect()
+            }
+        };
+        let values = values.map_err(|_| ReadError::schema_mismatch::<i128>("Integer"))?;
+        Ok(values.into_iter())
+    }
+    fn read_next_infallible(&mut self) -> Self::Read {
+        self.next().unwrap_or_default()
+    }
+    fn remaining(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
 struct PrefixVarIntCompressor;
 
 impl PrefixVarIntCompressor {
@@ -332,7 +786,3 @@ impl Compressor<u8> for BytesCompressor {
         Some(data.len())
     }
 }
-
-// TODO: Bitpacking https://crates.io/crates/bitpacking
-// TODO: Mayda https://crates.io/crates/mayda
-// TODO: https://lemire.me/blog/2012/09/12/fast-integer-compression-decoding-billions-of-integers-per-second/
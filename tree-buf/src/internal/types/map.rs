@@ -0,0 +1,178 @@
+use crate::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use std::vec::IntoIter;
+
+#[cfg(feature = "write")]
+#[derive(Debug, Default)]
+pub struct MapArrayWriter<K, V, M> {
+    len: <u64 as Writable>::WriterArray,
+    // Boxed to allow for recursion, eg: a HashMap of T within T
+    items: Option<Box<(K, V)>>,
+    _marker: Unowned<M>,
+}
+
+#[cfg(feature = "read")]
+pub struct MapArrayReader<K, V, M> {
+    len: IntoIter<u64>,
+    keys: K,
+    // Boxed to allow for recursion. See also MapArrayWriter
+    values: Box<V>,
+    _marker: Unowned<M>,
+}
+
+// HashMap and BTreeMap share an encoding, so either may be read as the other.
+macro_rules! impl_map {
+    ($Map:ident<K, V $(, $S:ident)?>, [$($KeyBound:tt)+] $(, [$($HasherBound:tt)+])?) => {
+        #[cfg(feature = "write")]
+        impl<K: Writable, V: Writable $(, $S: $($HasherBound)+)?> Writable for $Map<K, V $(, $S)?> {
+            type WriterArray = MapArrayWriter<K::WriterArray, V::WriterArray, Self>;
+            fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+                profile!("write_root");
+
+                write_usize(self.len(), stream);
+                match self.len() {
+                    0 => {}
+                    1 => {
+                        for key in self.keys() {
+                            stream.write_with_id(|stream| key.write_root(stream));
+                        }
+                        for value in self.values() {
+                            stream.write_with_id(|stream| value.write_root(stream));
+                        }
+                    }
+                    _ => {
+                        let mut keys_writer = K::WriterArray::default();
+                        for key in self.keys() {
+                            keys_writer.buffer(key);
+                        }
+                        stream.write_with_id(|stream| keys_writer.flush(stream));
+
+                        let mut values_writer = V::WriterArray::default();
+                        for value in self.values() {
+                            values_writer.buffer(value);
+                        }
+                        stream.write_with_id(|stream| values_writer.flush(stream));
+                    }
+                }
+
+                RootTypeId::Map
+            }
+        }
+
+        #[cfg(feature = "read")]
+        impl<'a, K: Readable<'a> + $($KeyBound)+ + Send, V: Readable<'a> + Send $(, $S: $($HasherBound)+)?> Readable<'a> for $Map<K, V $(, $S)?> {
+            type ReaderArray = Option<MapArrayReader<K::ReaderArray, V::ReaderArray, Self>>;
+            fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!("Readable::read");
+
+                let mut v = Default::default(); // TODO: (Performance) Capacity
+                match sticks {
+                    DynRootBranch::Map0 => Ok(v),
+                    DynRootBranch::Map1 { key, value } => {
+                        let (key, value) = parallel(move || K::read(*key, options), move || V::read(*value, options), options);
+                        v.insert(key.map_err(ReadError::in_element)?, value.map_err(ReadError::in_element)?);
+                        Ok(v)
+                    }
+                    DynRootBranch::Map { len, keys, values } => {
+                        let (keys, values) = parallel(|| K::ReaderArray::new(keys, options), || V::ReaderArray::new(values, options), options);
+                        let mut keys = keys.map_err(ReadError::in_element)?;
+                        let mut values = values.map_err(ReadError::in_element)?;
+                        for _ in 0..len {
+                            let key = keys.read_next().map_err(|e| Into::<ReadError>::into(e).in_element())?;
+                            let value = values.read_next().map_err(|e| Into::<ReadError>::into(e).in_element())?;
+                            if v.insert(key, value).is_some() {
+                                return Err(ReadError::InvalidFormat);
+                            }
+                        }
+                        Ok(v)
+                    }
+                    other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
+                }
+            }
+        }
+
+        #[cfg(feature = "write")]
+        impl<K: Writable, V: Writable $(, $S: $($HasherBound)+)?> WriterArray<$Map<K, V $(, $S)?>> for MapArrayWriter<K::WriterArray, V::WriterArray, $Map<K, V $(, $S)?>> {
+            fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b $Map<K, V $(, $S)?>) {
+                profile!("WriterArray::buffer");
+                self.len.buffer(&(value.len() as u64));
+                let (keys, values) = &mut **self.items.get_or_insert_with(Default::default);
+                for (key, value) in value.iter() {
+                    keys.buffer(key);
+                    values.buffer(value);
+                }
+            }
+            fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+                profile!("WriterArray::flush");
+                let Self { len, items, _marker } = self;
+                if let Some(items) = items {
+                    let (keys, values) = *items;
+                    stream.write_with_id(|stream| len.flush(stream));
+                    stream.write_with_id(|stream| keys.flush(stream));
+                    stream.write_with_id(|stream| values.flush(stream));
+                } else {
+                    stream.write_with_id(|_| ArrayTypeId::Void);
+                }
+                ArrayTypeId::Map
+            }
+        }
+
+        #[cfg(feature = "read")]
+        impl<'a, K: ReaderArray<'a>, V: ReaderArray<'a> $(, $S: $($HasherBound)+)?> ReaderArray<'a> for Option<MapArrayReader<K, V, $Map<K::Read, V::Read $(, $S)?>>>
+        where
+            K::Read: $($KeyBound)+,
+        {
+            type Read = $Map<K::Read, V::Read $(, $S)?>;
+            type Error = ReadError;
+
+            fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                profile!("ReaderArray::new");
+
+                match sticks {
+                    DynArrayBranch::Map0 => Ok(None),
+                    DynArrayBranch::Map { len, keys, values } => {
+                        let (keys, (values, len)) = parallel(
+                            || K::new(*keys, options),
+                            || parallel(|| V::new(*values, options), || <IntoIter<u64> as ReaderArray>::new(*len, options), options),
+                            options,
+                        );
+                        let keys = keys.map_err(ReadError::in_element)?;
+                        let values = Box::new(values.map_err(ReadError::in_element)?);
+                        let len = len?;
+                        Limits::new(options).collections::<(K::Read, V::Read)>(len.as_slice())?;
+                        Ok(Some(MapArrayReader {
+                            len,
+                            keys,
+                            values,
+                            _marker: Unowned::new(),
+                        }))
+                    }
+                    other => Err(ReadError::schema_mismatch::<Self::Read>(other.type_name())),
+                }
+            }
+            fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
+                if let Some(inner) = self {
+                    let len = inner.len.read_next_infallible();
+                    let mut result = <Self::Read as Default>::default(); // TODO: (Performance) capacity
+                    for _ in 0..len {
+                        let key = inner.keys.read_next().map_err(Into::<ReadError>::into)?;
+                        let value = inner.values.read_next().map_err(Into::<ReadError>::into)?;
+                        // TODO: read_next was made infallable for performance reasons,
+                        // but duplicate keys would seem a reason to fail. Ideally this could
+                        // have a Result<T, !> and perform well in the future.
+                        if result.insert(key, value).is_some() {
+                            return Err(ReadError::InvalidFormat);
+                        };
+                    }
+                    Ok(result)
+                } else {
+                    Ok(Default::default())
+                }
+            }
+        }
+    };
+}
+
+impl_map!(HashMap<K, V, S>, [Hash + Eq], [Default + BuildHasher]);
+impl_map!(BTreeMap<K, V>, [Ord]);
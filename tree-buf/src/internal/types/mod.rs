@@ -1,14 +1,20 @@
 pub mod array;
 pub mod array_fixed;
+pub mod blob;
 pub mod boolean;
 pub mod bytes;
+pub mod decimal;
 pub mod float;
-pub mod hashmap;
 pub mod ignore;
 pub mod integer;
+pub mod map;
 pub mod nullable;
 pub mod object;
 pub mod string;
 pub mod tuple;
+pub mod value;
 
-pub use {array::*, array_fixed::*, boolean::*, bytes::*, float::*, hashmap::*, ignore::*, integer::*, nullable::*, object::*, string::*, tuple::*};
+pub use {array::*, array_fixed::*, blob::*, boolean::*, bytes::*, decimal::*, float::*, ignore::*, map::*, nullable::*, object::*, string::*, tuple::*, value::*};
+// Only helpers for writing integers, which are used by other types
+#[cfg(feature = "write")]
+pub(crate) use integer::*;
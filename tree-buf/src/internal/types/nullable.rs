@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::vec::IntoIter;
 
 #[cfg(feature = "write")]
 impl<T: Writable> Writable for Option<T> {
@@ -13,9 +14,9 @@ impl<T: Writable> Writable for Option<T> {
 }
 
 #[cfg(feature = "read")]
-impl<T: Readable> Readable for Option<T> {
+impl<'a, T: Readable<'a>> Readable<'a> for Option<T> {
     type ReaderArray = Option<NullableReader<T::ReaderArray>>;
-    fn read(sticks: DynRootBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
         profile!("Readable::read");
         match sticks {
             DynRootBranch::Void => Ok(None),
@@ -28,7 +29,8 @@ impl<T: Readable> Readable for Option<T> {
 #[derive(Default)]
 pub struct NullableWriter<V> {
     opt: <bool as Writable>::WriterArray,
-    value: Option<V>,
+    // Boxed to allow for recursion, eg: Option<Box<T>> within T
+    value: Option<Box<V>>,
 }
 
 #[cfg(feature = "write")]
@@ -36,7 +38,7 @@ impl<T: Writable> WriterArray<Option<T>> for NullableWriter<T::WriterArray> {
     fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b Option<T>) {
         self.opt.buffer(&value.is_some());
         if let Some(value) = value {
-            self.value.get_or_insert_with(T::WriterArray::default).buffer(value);
+            self.value.get_or_insert_with(Default::default).buffer(value);
         }
     }
     fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
@@ -53,26 +55,27 @@ impl<T: Writable> WriterArray<Option<T>> for NullableWriter<T::WriterArray> {
 
 #[cfg(feature = "read")]
 pub struct NullableReader<T> {
-    opts: <bool as Readable>::ReaderArray,
-    values: T,
+    opts: IntoIter<bool>,
+    // Boxed to allow for recursion. See also NullableWriter
+    values: Box<T>,
 }
 
 #[cfg(feature = "read")]
-impl<T: ReaderArray> ReaderArray for Option<NullableReader<T>> {
+impl<'a, T: ReaderArray<'a>> ReaderArray<'a> for Option<NullableReader<T>> {
     type Read = Option<T::Read>;
     type Error = T::Error;
-    fn new(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
         profile!("ReaderArray::new");
 
         match sticks {
             DynArrayBranch::Nullable { opt, values } => {
                 let (opts, values) = parallel(|| <bool as Readable>::ReaderArray::new(*opt, options), || T::new(*values, options), options);
                 let opts = opts?;
-                let values = values?;
+                let values = Box::new(values?);
                 Ok(Some(NullableReader { opts, values }))
             }
             DynArrayBranch::Void => Ok(None),
-            _ => Err(ReadError::SchemaMismatch),
+            other => Err(ReadError::schema_mismatch::<Self::Read>(other.type_name())),
         }
     }
     fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
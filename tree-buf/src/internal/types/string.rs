@@ -1,6 +1,9 @@
 use crate::internal::encodings::varint::*;
 use crate::prelude::*;
 use rle::RLE;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
 use std::vec::IntoIter;
 
 // TODO: Consider compressed unicode (SCSU?) for String in general,
@@ -26,38 +29,58 @@ pub fn read_str<'a>(bytes: &'a [u8], offset: &'_ mut usize) -> ReadResult<&'a st
     read_str_len(len, bytes, offset)
 }
 
+#[cfg(feature = "write")]
+pub(crate) fn write_root_str<O: EncodeOptions>(value: &str, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+    match value.len() {
+        0 => RootTypeId::Str0,
+        1 => {
+            stream.bytes.push(value.as_bytes()[0]);
+            RootTypeId::Str1
+        }
+        2 => {
+            stream.bytes.extend_from_slice(value.as_bytes());
+            RootTypeId::Str2
+        }
+        3 => {
+            stream.bytes.extend_from_slice(value.as_bytes());
+            RootTypeId::Str3
+        }
+        _ => {
+            let b = value.as_bytes();
+            encode_prefix_varint(b.len() as u64, stream.bytes);
+            stream.bytes.extend_from_slice(b);
+            RootTypeId::Str
+        }
+    }
+}
+
 #[cfg(feature = "write")]
 impl Writable for String {
     type WriterArray = Vec<&'static str>;
     fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
-        let value = self.as_str();
-        match value.len() {
-            0 => RootTypeId::Str0,
-            1 => {
-                stream.bytes.push(value.as_bytes()[0]);
-                RootTypeId::Str1
-            }
-            2 => {
-                stream.bytes.extend_from_slice(value.as_bytes());
-                RootTypeId::Str2
-            }
-            3 => {
-                stream.bytes.extend_from_slice(value.as_bytes());
-                RootTypeId::Str3
-            }
-            _ => {
-                let b = value.as_bytes();
-                encode_prefix_varint(b.len() as u64, stream.bytes);
-                stream.bytes.extend_from_slice(b);
-                RootTypeId::Str
-            }
-        }
+        write_root_str(self, stream)
+    }
+}
+
+#[cfg(feature = "write")]
+impl Writable for &str {
+    type WriterArray = Vec<&'static str>;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        write_root_str(self, stream)
     }
 }
 
 #[cfg(feature = "write")]
-impl WriterArray<String> for Vec<&'static str> {
-    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b String) {
+impl<'a> Writable for Cow<'a, str> {
+    type WriterArray = Vec<&'static str>;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        write_root_str(self, stream)
+    }
+}
+
+#[cfg(feature = "write")]
+impl<T: AsRef<str>> WriterArray<T> for Vec<&'static str> {
+    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b T) {
         // TODO: Working around lifetime issues for lack of GAT
         // A quick check makes this appear to be sound, since the signature
         // requires that the value outlive self.
@@ -65,7 +88,7 @@ impl WriterArray<String> for Vec<&'static str> {
         // The big safety problem is that whe then give these references
         // away when flushing. We happen to know that nothing saves the references,
         // but when things like threading come into play it's hard to know.
-        self.push(unsafe { std::mem::transmute(value.as_str()) });
+        self.push(unsafe { std::mem::transmute::<&'b str, &'static str>(value.as_ref()) });
     }
     fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
         profile!("WriterArray::flush");
@@ -80,24 +103,32 @@ impl WriterArray<String> for Vec<&'static str> {
     }
 }
 
+#[cfg(feature = "write")]
+impl<'a> BitEq for &'a str {
+    type Bits = &'a str;
+    fn bits(&self) -> Self::Bits {
+        self
+    }
+}
+
 #[cfg(feature = "read")]
-impl Readable for String {
-    // TODO: Use lifetimes to make this read lazy rather than IntoIter
-    type ReaderArray = IntoIter<String>;
-    fn read(sticks: DynRootBranch<'_>, _options: &impl DecodeOptions) -> ReadResult<Self> {
+impl<'a: 'b, 'b> Readable<'a> for &'b str {
+    type ReaderArray = IntoIter<&'b str>;
+    fn read(sticks: DynRootBranch<'a>, _options: &impl DecodeOptions) -> ReadResult<Self> {
         profile!("Readable::read");
         match sticks {
-            DynRootBranch::String(s) => Ok(s.to_owned()),
-            _ => Err(ReadError::SchemaMismatch),
+            DynRootBranch::String(s) => Ok(s),
+            other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
         }
     }
 }
 
+// TODO: Use lifetimes to make this read lazy rather than IntoIter
 #[cfg(feature = "read")]
-impl InfallibleReaderArray for IntoIter<String> {
-    type Read = String;
+impl<'a: 'b, 'b> InfallibleReaderArray<'a> for IntoIter<&'b str> {
+    type Read = &'b str;
 
-    fn new_infallible(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
         profile!("ReaderArray::new");
 
         match sticks {
@@ -105,7 +136,12 @@ impl InfallibleReaderArray for IntoIter<String> {
                 #[cfg(feature = "profile")]
                 let _g = flame::start_guard("String");
 
-                let strs = read_all(&bytes, |b, o| read_str(b, o).and_then(|v| Ok(v.to_owned())))?;
+                let limits = Limits::new(options);
+                let strs = read_all(bytes.as_slice(), |bytes, offset| {
+                    let s = read_str(bytes, offset)?;
+                    limits.string(s.len())?;
+                    Ok(s)
+                })?;
                 Ok(strs.into_iter())
             }
             DynArrayBranch::RLE { runs, values } => {
@@ -118,12 +154,119 @@ impl InfallibleReaderArray for IntoIter<String> {
                 let all = dict.collect::<Vec<_>>();
                 Ok(all.into_iter())
             }
-            _ => Err(ReadError::SchemaMismatch),
+            other => Err(ReadError::schema_mismatch::<&str>(other.type_name())),
         }
     }
     fn read_next_infallible(&mut self) -> Self::Read {
         self.next().unwrap_or_default()
     }
+    fn remaining(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> Readable<'a> for String {
+    type ReaderArray = IntoIter<String>;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        <&str as Readable>::read(sticks, options).map(ToOwned::to_owned)
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> InfallibleReaderArray<'a> for IntoIter<String> {
+    type Read = String;
+
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+        let strs = <IntoIter<&str> as InfallibleReaderArray>::new_infallible(sticks, options)?;
+        let all = strs.map(ToOwned::to_owned).collect::<Vec<_>>();
+        Ok(all.into_iter())
+    }
+    fn read_next_infallible(&mut self) -> Self::Read {
+        self.next().unwrap_or_default()
+    }
+    fn remaining(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a: 'b, 'b> Readable<'a> for Cow<'b, str> {
+    type ReaderArray = IntoIter<Cow<'b, str>>;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        <&str as Readable>::read(sticks, options).map(Cow::Borrowed)
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a: 'b, 'b> InfallibleReaderArray<'a> for IntoIter<Cow<'b, str>> {
+    type Read = Cow<'b, str>;
+
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+        let strs = <IntoIter<&str> as InfallibleReaderArray>::new_infallible(sticks, options)?;
+        let all = strs.map(Cow::Borrowed).collect::<Vec<_>>();
+        Ok(all.into_iter())
+    }
+    fn read_next_infallible(&mut self) -> Self::Read {
+        self.next().unwrap_or_default()
+    }
+    fn remaining(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+// Shared and boxed strings are written the same as String
+macro_rules! impl_str_pointer {
+    ($($Pointer:ident),+) => {
+        $(
+            #[cfg(feature = "write")]
+            impl Writable for $Pointer<str> {
+                type WriterArray = Vec<&'static str>;
+                fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+                    write_root_str(self, stream)
+                }
+            }
+
+            #[cfg(feature = "read")]
+            impl<'a> Readable<'a> for $Pointer<str> {
+                type ReaderArray = StrPointerReaderArray<'a, $Pointer<str>>;
+                fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+                    profile!("Readable::read");
+                    <&str as Readable>::read(sticks, options).map(Into::into)
+                }
+            }
+        )+
+    };
+}
+
+impl_str_pointer!(Box, Rc, Arc);
+
+// Holds the borrowed strs rather than the pointers, since
+// Rc is not Send and ReaderArray requires it.
+#[cfg(feature = "read")]
+pub struct StrPointerReaderArray<'b, P> {
+    strs: IntoIter<&'b str>,
+    _marker: Unowned<P>,
+}
+
+#[cfg(feature = "read")]
+impl<'a: 'b, 'b, P: From<&'b str>> InfallibleReaderArray<'a> for StrPointerReaderArray<'b, P> {
+    type Read = P;
+
+    fn new_infallible(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+        Ok(StrPointerReaderArray {
+            strs: InfallibleReaderArray::new_infallible(sticks, options)?,
+            _marker: Unowned::new(),
+        })
+    }
+    fn read_next_infallible(&mut self) -> Self::Read {
+        self.strs.next().unwrap_or_default().into()
+    }
 }
 
 #[cfg(feature = "write")]
@@ -65,8 +65,8 @@ macro_rules! parallel_read {
 macro_rules! impl_tuple {
     ($count:expr, $trid:expr, $taid:expr, $($ts:ident, $ti:tt,)+) => {
         #[cfg(feature = "write")]
-        impl <$($ts: Writable),+> Writable for ($($ts),+) {
-            type WriterArray=($($ts::WriterArray),+);
+        impl <$($ts: Writable),+> Writable for ($($ts,)+) {
+            type WriterArray=($($ts::WriterArray,)+);
             fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
                 profile!("Writable::write_root");
                 $(
@@ -77,8 +77,8 @@ macro_rules! impl_tuple {
         }
 
         #[cfg(feature = "write")]
-        impl<$($ts: Writable),+> WriterArray<($($ts),+)> for ($($ts::WriterArray),+) {
-            fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b ($($ts),+)) {
+        impl<$($ts: Writable),+> WriterArray<($($ts,)+)> for ($($ts::WriterArray,)+) {
+            fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b ($($ts,)+)) {
                 $(
                     tuple_index!(self, $ti).buffer(&tuple_index!(value, $ti));
                 )+
@@ -96,17 +96,15 @@ macro_rules! impl_tuple {
         }
 
         #[cfg(feature = "read")]
-        impl <$($ts: Readable + Send),+> Readable for ($($ts),+)
-        // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
-        where $(ReadError : From<<$ts::ReaderArray as ReaderArray>::Error>),+ {
-            type ReaderArray=($($ts::ReaderArray),+);
-            fn read(sticks: DynRootBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        impl <'a, $($ts: Readable<'a> + Send),+> Readable<'a> for ($($ts,)+) {
+            type ReaderArray=($($ts::ReaderArray,)+);
+            fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
                 profile!("Readable::read");
                 match sticks {
                     DynRootBranch::Tuple { mut fields } => {
                         // See also abb368f2-6c99-4c44-8f9f-4b00868adaaf
                         if fields.len() != $count {
-                            return Err(ReadError::SchemaMismatch)
+                            return Err(ReadError::schema_mismatch::<Self>("Tuple"))
                         }
                         let mut fields = fields.drain(..);
 
@@ -118,30 +116,28 @@ macro_rules! impl_tuple {
 
                         parallel_read!(options, $($ts),*);
 
-                        Ok(($($ts?),*))
+                        Ok(($($ts.map_err(|e| e.in_index($ti))?,)*))
                     },
-                    _ => Err(ReadError::SchemaMismatch),
+                    other => Err(ReadError::schema_mismatch::<Self>(other.type_name())),
                 }
             }
         }
 
         #[cfg(feature = "read")]
-        impl <$($ts: ReaderArray),+> ReaderArray for ($($ts),+)
-        // Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
-        where $(ReadError : From<$ts::Error>),+ {
-            type Read=($($ts::Read),+);
+        impl <'a, $($ts: ReaderArray<'a>),+> ReaderArray<'a> for ($($ts,)+) {
+            type Read=($($ts::Read,)+);
             // TODO: It would be nice to know somehow whether or not
             // all the fields are infallible types. Perhaps specialization
             // can achieve this.
             type Error=ReadError;
-            fn new(sticks: DynArrayBranch<'_>, options: &impl DecodeOptions) -> ReadResult<Self> {
+            fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
                 profile!("ReaderArray::new");
 
                 match sticks {
                     DynArrayBranch::Tuple { mut fields } => {
                         // See also abb368f2-6c99-4c44-8f9f-4b00868adaaf
                         if fields.len() != $count {
-                            return Err(ReadError::SchemaMismatch)
+                            return Err(ReadError::schema_mismatch::<Self::Read>("Tuple"))
                         }
                         let mut fields = fields.drain(..);
 
@@ -153,93 +149,58 @@ macro_rules! impl_tuple {
 
                         parallel_new!(options, $($ts),*);
 
-                        Ok(($($ts?),*))
+                        Ok(($($ts.map_err(|e| e.in_index($ti))?,)*))
                     },
-                    _ => Err(ReadError::SchemaMismatch)
+                    other => Err(ReadError::schema_mismatch::<Self::Read>(other.type_name())),
                 }
             }
             fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
                 Ok(($(
-                    tuple_index!(self, $ti).read_next()?,
+                    tuple_index!(self, $ti).read_next().map_err(|e| Into::<ReadError>::into(e).in_index($ti))?,
                 )+))
             }
         }
     };
 }
 
-// TODO: Consider 0 and 1 sized tuples.
-// These should probably be no serialization at all,
-// and pass-through serialization respectively and just
-// not use the tuple construct. The tuple construct isn't invalid
-// though, which opens considerations for matching either for a schema
-// which may not be trivial - like a recursive descent parser.
+// TODO: Consider 0 sized tuples.
+// These should probably be no serialization at all.
+// 1 sized tuples use the tuple construct so that they can share
+// a schema with tuple structs having a single field.
+impl_tuple!(1, RootTypeId::Tuple1, ArrayTypeId::Tuple1, T0, 0,);
 impl_tuple!(2, RootTypeId::Tuple2, ArrayTypeId::Tuple2, T0, 0, T1, 1,);
 impl_tuple!(3, RootTypeId::Tuple3, ArrayTypeId::Tuple3, T0, 0, T1, 1, T2, 2,);
 impl_tuple!(4, RootTypeId::Tuple4, ArrayTypeId::Tuple4, T0, 0, T1, 1, T2, 2, T3, 3,);
 impl_tuple!(5, RootTypeId::Tuple5, ArrayTypeId::Tuple5, T0, 0, T1, 1, T2, 2, T3, 3, T4, 4,);
 impl_tuple!(6, RootTypeId::Tuple6, ArrayTypeId::Tuple6, T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,);
 
-// TODO: Support tuple structs in the macro
-
-// TODO: Move these into macro
-
-impl<T, T0: Compressor<T>> CompressorSet<T> for (T0,) {
-    fn len(&self) -> usize {
-        1
-    }
-    fn fast_size_for(&self, compressor: usize, data: &[T]) -> Option<usize> {
-        match compressor {
-            0 => self.0.fast_size_for(data),
-            _ => unreachable!("No compressor at that index"),
-        }
-    }
-    fn compress<O: EncodeOptions>(&self, compressor: usize, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
-        match compressor {
-            0 => self.0.compress(data, stream),
-            _ => unreachable!("No compressor at that index"),
-        }
-    }
-}
-
-impl<T, T0: Compressor<T>, T1: Compressor<T>> CompressorSet<T> for (T0, T1) {
-    fn len(&self) -> usize {
-        2
-    }
-    fn fast_size_for(&self, compressor: usize, data: &[T]) -> Option<usize> {
-        match compressor {
-            0 => self.0.fast_size_for(data),
-            1 => self.1.fast_size_for(data),
-            _ => unreachable!("No compressor at that index"),
-        }
-    }
-    fn compress<O: EncodeOptions>(&self, compressor: usize, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
-        match compressor {
-            0 => self.0.compress(data, stream),
-            1 => self.1.compress(data, stream),
-            _ => unreachable!("No compressor at that index"),
+macro_rules! impl_compressor_set {
+    ($count:expr, $($ts:ident, $ti:tt,)+) => {
+        impl<T, $($ts: Compressor<T>),+> CompressorSet<T> for ($($ts,)+) {
+            fn len(&self) -> usize {
+                $count
+            }
+            fn fast_size_for(&self, compressor: usize, data: &[T]) -> Option<usize> {
+                match compressor {
+                    $($ti => tuple_index!(self, $ti).fast_size_for(data),)+
+                    _ => unreachable!("No compressor at that index"),
+                }
+            }
+            fn compress<O: EncodeOptions>(&self, compressor: usize, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
+                match compressor {
+                    $($ti => tuple_index!(self, $ti).compress(data, stream),)+
+                    _ => unreachable!("No compressor at that index"),
+                }
+            }
         }
-    }
+    };
 }
 
-
-impl<T, T0: Compressor<T>, T1: Compressor<T>, T2: Compressor<T>> CompressorSet<T> for (T0, T1, T2) {
-    fn len(&self) -> usize {
-        3
-    }
-    fn fast_size_for(&self, compressor: usize, data: &[T]) -> Option<usize> {
-        match compressor {
-            0 => self.0.fast_size_for(data),
-            1 => self.1.fast_size_for(data),
-            2 => self.2.fast_size_for(data),
-            _ => unreachable!("No compressor at that index"),
-        }
-    }
-    fn compress<O: EncodeOptions>(&self, compressor: usize, data: &[T], stream: &mut WriterStream<'_, O>) -> Result<ArrayTypeId, ()> {
-        match compressor {
-            0 => self.0.compress(data, stream),
-            1 => self.1.compress(data, stream),
-            2 => self.2.compress(data, stream),
-            _ => unreachable!("No compressor at that index"),
-        }
-    }
-}
\ No newline at end of file
+impl_compressor_set!(1, T0, 0,);
+impl_compressor_set!(2, T0, 0, T1, 1,);
+impl_compressor_set!(3, T0, 0, T1, 1, T2, 2,);
+impl_compressor_set!(4, T0, 0, T1, 1, T2, 2, T3, 3,);
+impl_compressor_set!(5, T0, 0, T1, 1, T2, 2, T3, 3, T4, 4,);
+impl_compressor_set!(6, T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5,);
+impl_compressor_set!(7, T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6,);
+impl_compressor_set!(8, T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7,);
@@ -0,0 +1,742 @@
+#[cfg(feature = "write")]
+use crate::internal::encodings::varint::encode_prefix_varint;
+use crate::prelude::*;
+use std::collections::BTreeMap;
+#[cfg(feature = "write")]
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+#[cfg(feature = "read")]
+use std::vec::IntoIter;
+#[cfg(feature = "write")]
+use zigzag::ZigZag;
+
+/// Any tree-buf data, for reading and writing without a schema known at compile time.
+///
+/// Reading a `Value` succeeds for any document, making it useful for generic tooling
+/// and debugging. Some distinctions in the format are not preserved. Eg: tuples are read
+/// as arrays, and objects in an array which are missing a field read that field as `Null`.
+///
+/// Within an array, values of different kinds (eg: strings mixed with integers) are written
+/// as an enum with a variant for each kind, and so are read back as `Value::Enum`. The exception
+/// is when each index has a consistent kind (as with tuples), in which case the arrays are written as tuples.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Value {
+    #[default]
+    Null,
+    Bool(bool),
+    Integer(i128),
+    /// An integer greater than `i128::MAX`. Smaller integers are always read as `Integer`.
+    UInteger(u128),
+    Float(f64),
+    Decimal(Decimal),
+    String(String),
+    Blob(Vec<u8>),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+    /// The keys of a map may be any value, so the entries are kept in order rather than hashed.
+    Map(Vec<(Value, Value)>),
+    Enum {
+        discriminant: String,
+        value: Box<Value>,
+    },
+}
+
+// See also: fadaec14-35ad-4dc1-b6dc-6106ab811669
+#[cfg(feature = "write")]
+macro_rules! fields_type_id {
+    ($T:ident, $num_fields:expr, $stream:expr, [$($i:literal => $id:ident,)+] $N:ident) => {
+        match $num_fields {
+            $($i => $T::$id,)+
+            n => {
+                encode_prefix_varint(n as u64 - 9, $stream.bytes);
+                $T::$N
+            }
+        }
+    };
+}
+
+#[cfg(feature = "write")]
+macro_rules! object_type_id {
+    ($T:ident, $num_fields:expr, $stream:expr) => {
+        fields_type_id!($T, $num_fields, $stream, [
+            0 => Obj0, 1 => Obj1, 2 => Obj2, 3 => Obj3, 4 => Obj4, 5 => Obj5, 6 => Obj6, 7 => Obj7, 8 => Obj8,
+        ] ObjN)
+    };
+}
+
+// Only used for arrays of mixed kinds, which have at least 2 items.
+#[cfg(feature = "write")]
+macro_rules! tuple_type_id {
+    ($T:ident, $num_fields:expr, $stream:expr) => {
+        fields_type_id!($T, $num_fields, $stream, [
+            1 => Tuple1, 2 => Tuple2, 3 => Tuple3, 4 => Tuple4, 5 => Tuple5, 6 => Tuple6, 7 => Tuple7, 8 => Tuple8,
+        ] TupleN)
+    };
+}
+
+#[cfg(feature = "write")]
+impl Writable for Value {
+    type WriterArray = ValueWriterArray;
+    fn write_root<O: EncodeOptions>(&self, stream: &mut WriterStream<'_, O>) -> RootTypeId {
+        profile!("write_root");
+        match self {
+            Value::Null => RootTypeId::Void,
+            Value::Bool(value) => value.write_root(stream),
+            Value::Integer(value) => write_root_sint128(*value, stream),
+            Value::UInteger(value) => write_root_uint128(*value, stream),
+            Value::Float(value) => {
+                // Not using the Writable impl for f64, because that writes
+                // eg: 1.0 the same as the integer 1.
+                if value.is_nan() {
+                    RootTypeId::NaN
+                } else {
+                    stream.bytes.extend_from_slice(&value.to_le_bytes());
+                    RootTypeId::F64
+                }
+            }
+            Value::Decimal(value) => value.write_root(stream),
+            Value::String(value) => write_root_str(value, stream),
+            Value::Blob(value) => write_root_blob(value, stream),
+            Value::Array(values) => match values.len() {
+                0 => RootTypeId::Array0,
+                1 => {
+                    stream.write_with_id(|stream| values[0].write_root(stream));
+                    RootTypeId::Array1
+                }
+                len if is_tuple(&[values]) => {
+                    let id = tuple_type_id!(RootTypeId, len, stream);
+                    for value in values.iter() {
+                        stream.write_with_id(|stream| value.write_root(stream));
+                    }
+                    id
+                }
+                _ => {
+                    write_usize(values.len(), stream);
+                    let values = values.iter().collect::<Vec<_>>();
+                    stream.write_with_id(|stream| flush_values(&values, stream));
+                    RootTypeId::ArrayN
+                }
+            },
+            Value::Object(fields) => {
+                let id = object_type_id!(RootTypeId, fields.len(), stream);
+                for (name, value) in fields.iter() {
+                    write_ident(name, stream);
+                    stream.write_with_id(|stream| value.write_root(stream));
+                }
+                id
+            }
+            Value::Map(entries) => {
+                write_usize(entries.len(), stream);
+                match entries.len() {
+                    0 => {}
+                    1 => {
+                        let (key, value) = &entries[0];
+                        stream.write_with_id(|stream| key.write_root(stream));
+                        stream.write_with_id(|stream| value.write_root(stream));
+                    }
+                    _ => {
+                        let keys = entries.iter().map(|(k, _)| k).collect::<Vec<_>>();
+                        stream.write_with_id(|stream| flush_values(&keys, stream));
+                        let values = entries.iter().map(|(_, v)| v).collect::<Vec<_>>();
+                        stream.write_with_id(|stream| flush_values(&values, stream));
+                    }
+                }
+                RootTypeId::Map
+            }
+            Value::Enum { discriminant, value } => {
+                write_ident(discriminant, stream);
+                stream.write_with_id(|stream| value.write_root(stream));
+                RootTypeId::Enum
+            }
+        }
+    }
+}
+
+#[cfg(feature = "write")]
+#[derive(Debug, Default)]
+pub struct ValueWriterArray {
+    // The kinds of all values must be known before choosing how to write them,
+    // so nothing can be written until flush.
+    values: Vec<Value>,
+}
+
+#[cfg(feature = "write")]
+impl WriterArray<Value> for ValueWriterArray {
+    fn buffer<'a, 'b: 'a>(&'a mut self, value: &'b Value) {
+        self.values.push(value.clone());
+    }
+    fn flush<O: EncodeOptions>(self, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+        profile!("WriterArray::flush");
+        let values = self.values.iter().collect::<Vec<_>>();
+        flush_values(&values, stream)
+    }
+}
+
+#[cfg(feature = "write")]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Kind {
+    Null,
+    Bool,
+    Integer,
+    Float,
+    Decimal,
+    String,
+    Blob,
+    Array,
+    Object,
+    Map,
+    Enum,
+}
+
+#[cfg(feature = "write")]
+impl Kind {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Kind::Null,
+            Value::Bool(_) => Kind::Bool,
+            Value::Integer(_) | Value::UInteger(_) => Kind::Integer,
+            Value::Float(_) => Kind::Float,
+            Value::Decimal(_) => Kind::Decimal,
+            Value::String(_) => Kind::String,
+            Value::Blob(_) => Kind::Blob,
+            Value::Array(_) => Kind::Array,
+            Value::Object(_) => Kind::Object,
+            Value::Map(_) => Kind::Map,
+            Value::Enum { .. } => Kind::Enum,
+        }
+    }
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Null => "null",
+            Kind::Bool => "bool",
+            Kind::Integer => "integer",
+            Kind::Float => "float",
+            Kind::Decimal => "decimal",
+            Kind::String => "string",
+            Kind::Blob => "blob",
+            Kind::Array => "array",
+            Kind::Object => "object",
+            Kind::Map => "map",
+            Kind::Enum => "enum",
+        }
+    }
+}
+
+#[cfg(feature = "write")]
+fn flush_values<O: EncodeOptions>(values: &[&Value], stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+    let kinds = values.iter().map(|v| Kind::of(v)).filter(|k| *k != Kind::Null).collect::<BTreeSet<_>>();
+    let has_null = values.iter().any(|v| **v == Value::Null);
+
+    match (kinds.len(), has_null) {
+        (0, _) => ArrayTypeId::Void,
+        (1, false) => flush_kind(*kinds.iter().next().unwrap(), values, stream),
+        (1, true) => {
+            let opt = values.iter().map(|v| **v != Value::Null).collect::<Vec<_>>();
+            let present = values.iter().copied().filter(|v| **v != Value::Null).collect::<Vec<_>>();
+            stream.write_with_id(|stream| opt.flush(stream));
+            stream.write_with_id(|stream| flush_kind(*kinds.iter().next().unwrap(), &present, stream));
+            ArrayTypeId::Nullable
+        }
+        _ => {
+            let mut variants = Vec::<(&str, Vec<&Value>)>::new();
+            let discriminants = values
+                .iter()
+                .map(|value| {
+                    let name = Kind::of(value).name();
+                    variant_index(&mut variants, name, value)
+                })
+                .collect::<Vec<_>>();
+            flush_enum(discriminants, variants, stream)
+        }
+    }
+}
+
+/// Arrays which have the same length and a consistent kind for each index,
+/// but a mix of kinds overall, are written as tuples. Eg: [1, "a"] and [2, "b"]
+/// This preserves the kinds of their items, which would otherwise be read as an enum.
+#[cfg(feature = "write")]
+fn is_tuple(arrays: &[&Vec<Value>]) -> bool {
+    let len = arrays[0].len();
+    if len < 2 || arrays.iter().any(|a| a.len() != len) {
+        return false;
+    }
+    let mut kinds = BTreeSet::new();
+    for i in 0..len {
+        let column = arrays.iter().map(|a| Kind::of(&a[i])).filter(|k| *k != Kind::Null).collect::<BTreeSet<_>>();
+        if column.len() > 1 {
+            return false;
+        }
+        kinds.extend(column);
+    }
+    kinds.len() > 1
+}
+
+/// Adds the value to the variant with the given name, returning the discriminant
+#[cfg(feature = "write")]
+fn variant_index<'v>(variants: &mut Vec<(&'v str, Vec<&'v Value>)>, name: &'v str, value: &'v Value) -> u64 {
+    let index = match variants.iter().position(|(n, _)| *n == name) {
+        Some(index) => index,
+        None => {
+            variants.push((name, Vec::new()));
+            variants.len() - 1
+        }
+    };
+    variants[index].1.push(value);
+    index as u64
+}
+
+/// Writes values which are all the same kind. Null is handled by the caller.
+#[cfg(feature = "write")]
+fn flush_kind<O: EncodeOptions>(kind: Kind, values: &[&Value], stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+    match kind {
+        Kind::Null => ArrayTypeId::Void,
+        Kind::Bool => {
+            let bools = values.iter().map(|v| matches!(v, Value::Bool(true))).collect::<Vec<_>>();
+            bools.flush(stream)
+        }
+        Kind::Integer => {
+            if values.iter().all(|v| !matches!(v, Value::Integer(i) if *i < 0)) {
+                let ints = values
+                    .iter()
+                    .map(|v| match v {
+                        Value::Integer(i) => *i as u128,
+                        Value::UInteger(u) => *u,
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
+                write_uints128(&ints, stream, write_uints)
+            } else if values.iter().any(|v| matches!(v, Value::UInteger(_))) {
+                // There is no array of integers which holds both negative numbers and those
+                // greater than i128::MAX, so these are split into an enum of each.
+                let mut variants = Vec::<(&str, Vec<&Value>)>::new();
+                let discriminants = values
+                    .iter()
+                    .map(|value| {
+                        let name = if let Value::UInteger(_) = value { "uinteger" } else { "integer" };
+                        variant_index(&mut variants, name, value)
+                    })
+                    .collect::<Vec<_>>();
+                flush_enum(discriminants, variants, stream)
+            } else {
+                let zigzag = values
+                    .iter()
+                    .map(|v| match v {
+                        Value::Integer(i) => ZigZag::encode(*i),
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<u128>>();
+                stream.write_with_id(|stream| write_uints128(&zigzag, stream, write_uints));
+                ArrayTypeId::Zigzag
+            }
+        }
+        Kind::Float => {
+            let floats = values
+                .iter()
+                .map(|v| match v {
+                    Value::Float(f) => *f,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+            floats.flush(stream)
+        }
+        Kind::Decimal => {
+            let mut writer = DecimalArrayWriter::default();
+            for value in values.iter() {
+                if let Value::Decimal(decimal) = value {
+                    writer.buffer(decimal);
+                }
+            }
+            WriterArray::<Decimal>::flush(writer, stream)
+        }
+        Kind::String => {
+            let mut writer = <String as Writable>::WriterArray::default();
+            for value in values.iter() {
+                if let Value::String(s) = value {
+                    writer.buffer(s);
+                }
+            }
+            WriterArray::<String>::flush(writer, stream)
+        }
+        Kind::Blob => {
+            let mut writer = BlobArrayWriter::default();
+            for value in values.iter() {
+                if let Value::Blob(bytes) = value {
+                    writer.buffer_bytes(bytes);
+                }
+            }
+            WriterArray::<Blob>::flush(writer, stream)
+        }
+        Kind::Array => {
+            let arrays = values
+                .iter()
+                .map(|v| match v {
+                    Value::Array(array) => array,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+            if is_tuple(&arrays) {
+                let id = tuple_type_id!(ArrayTypeId, arrays[0].len(), stream);
+                for i in 0..arrays[0].len() {
+                    let column = arrays.iter().map(|a| &a[i]).collect::<Vec<_>>();
+                    stream.write_with_id(|stream| flush_values(&column, stream));
+                }
+                return id;
+            }
+            let len = arrays.iter().map(|a| a.len() as u64).collect::<Vec<_>>();
+            let items = arrays.iter().flat_map(|a| a.iter()).collect::<Vec<_>>();
+            // See also VecArrayWriter
+            if len.iter().all(|l| *l == len[0]) {
+                write_usize(len[0] as usize, stream);
+                stream.write_with_id(|stream| flush_values(&items, stream));
+                ArrayTypeId::ArrayFixed
+            } else {
+                stream.write_with_id(|stream| len.flush(stream));
+                stream.write_with_id(|stream| flush_values(&items, stream));
+                ArrayTypeId::ArrayVar
+            }
+        }
+        Kind::Object => {
+            let mut names = BTreeSet::new();
+            for value in values.iter() {
+                if let Value::Object(fields) = value {
+                    names.extend(fields.keys().map(|k| k.as_str()));
+                }
+            }
+            static NULL: Value = Value::Null;
+            let id = object_type_id!(ArrayTypeId, names.len(), stream);
+            for name in names {
+                let column = values
+                    .iter()
+                    .map(|v| match v {
+                        Value::Object(fields) => fields.get(name).unwrap_or(&NULL),
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
+                write_ident(name, stream);
+                stream.write_with_id(|stream| flush_values(&column, stream));
+            }
+            id
+        }
+        Kind::Map => {
+            let mut len = Vec::<u64>::new();
+            let mut keys = Vec::new();
+            let mut items = Vec::new();
+            for value in values.iter() {
+                if let Value::Map(entries) = value {
+                    len.push(entries.len() as u64);
+                    for (key, item) in entries.iter() {
+                        keys.push(key);
+                        items.push(item);
+                    }
+                }
+            }
+            // See also MapArrayWriter
+            stream.write_with_id(|stream| len.flush(stream));
+            stream.write_with_id(|stream| flush_values(&keys, stream));
+            stream.write_with_id(|stream| flush_values(&items, stream));
+            ArrayTypeId::Map
+        }
+        Kind::Enum => {
+            let mut variants = Vec::<(&str, Vec<&Value>)>::new();
+            let discriminants = values
+                .iter()
+                .map(|v| match v {
+                    Value::Enum { discriminant, value } => variant_index(&mut variants, discriminant, value),
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+            flush_enum(discriminants, variants, stream)
+        }
+    }
+}
+
+#[cfg(feature = "write")]
+fn flush_enum<O: EncodeOptions>(discriminants: Vec<u64>, variants: Vec<(&str, Vec<&Value>)>, stream: &mut WriterStream<'_, O>) -> ArrayTypeId {
+    // See also the Write derive for enums
+    encode_prefix_varint(variants.len() as u64, stream.bytes);
+    stream.write_with_id(|stream| discriminants.flush(stream));
+    for (name, values) in variants {
+        write_ident(name, stream);
+        stream.write_with_id(|stream| flush_values(&values, stream));
+    }
+    ArrayTypeId::Enum
+}
+
+#[cfg(feature = "read")]
+fn uinteger(value: u128) -> Value {
+    i128::try_from(value).map_or(Value::UInteger(value), Value::Integer)
+}
+
+#[cfg(feature = "read")]
+impl<'a> Readable<'a> for Value {
+    type ReaderArray = ValueReaderArray<'a>;
+    fn read(sticks: DynRootBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("Readable::read");
+        let value = match sticks {
+            DynRootBranch::Object { fields } => {
+                let mut result = BTreeMap::new();
+                for (name, value) in fields {
+                    result.insert(name.to_owned(), Value::read(value, options)?);
+                }
+                Value::Object(result)
+            }
+            DynRootBranch::Tuple { fields } => {
+                let fields: ReadResult<Vec<_>> = fields.into_iter().map(|f| Value::read(f, options)).collect();
+                Value::Array(fields?)
+            }
+            DynRootBranch::Enum { discriminant, value } => Value::Enum {
+                discriminant: discriminant.to_owned(),
+                value: Box::new(Value::read(*value, options)?),
+            },
+            DynRootBranch::Array0 => Value::Array(Vec::new()),
+            DynRootBranch::Array1(value) => Value::Array(vec![Value::read(*value, options)?]),
+            DynRootBranch::Array { len, values } => {
+                Limits::new(options).collection::<Value>(len)?;
+                let mut reader = ValueReader::new(values, options)?;
+                let values: ReadResult<Vec<_>> = (0..len).map(|_| reader.read_next()).collect();
+                Value::Array(values?)
+            }
+            DynRootBranch::Integer(RootInteger::U(value)) => Value::Integer(value.into()),
+            DynRootBranch::Integer(RootInteger::S(value)) => Value::Integer(value.into()),
+            DynRootBranch::Integer(RootInteger::U128(value)) => uinteger(value),
+            DynRootBranch::Integer(RootInteger::S128(value)) => Value::Integer(value),
+            DynRootBranch::Boolean(value) => Value::Bool(value),
+            DynRootBranch::Float(RootFloat::F64(value)) => Value::Float(value),
+            DynRootBranch::Float(RootFloat::F32(value)) => Value::Float(value.into()),
+            DynRootBranch::Float(RootFloat::NaN) => Value::Float(f64::NAN),
+            DynRootBranch::Decimal { .. } => Value::Decimal(Decimal::read(sticks, options)?),
+            DynRootBranch::Void => Value::Null,
+            DynRootBranch::String(value) => Value::String(value.to_owned()),
+            DynRootBranch::Blob(bytes) => Value::Blob(bytes.decompress(&Limits::new(options))?.into_owned()),
+            DynRootBranch::Map0 => Value::Map(Vec::new()),
+            DynRootBranch::Map1 { key, value } => Value::Map(vec![(Value::read(*key, options)?, Value::read(*value, options)?)]),
+            DynRootBranch::Map { len, keys, values } => {
+                Limits::new(options).collection::<(Value, Value)>(len)?;
+                let mut keys = ValueReader::new(keys, options)?;
+                let mut values = ValueReader::new(values, options)?;
+                let entries: ReadResult<Vec<_>> = (0..len).map(|_| Ok((keys.read_next()?, values.read_next()?))).collect();
+                Value::Map(entries?)
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "read")]
+pub struct ValueReaderArray<'a>(ValueReader<'a>);
+
+/// Mirrors the structure of a DynArrayBranch, producing one row at a time.
+#[cfg(feature = "read")]
+enum ValueReader<'a> {
+    Null,
+    Bool(IntoIter<bool>),
+    Integer(IntoIter<i128>),
+    UInteger(IntoIter<u128>),
+    Float(IntoIter<f64>),
+    Decimal(DecimalArrayReader),
+    String(IntoIter<&'a str>),
+    Blob(BlobArrayReader<'a>),
+    Array {
+        len: FixedOrVariableLength,
+        values: Box<ValueReader<'a>>,
+    },
+    EmptyArray,
+    Map {
+        len: IntoIter<u64>,
+        keys: Box<ValueReader<'a>>,
+        values: Box<ValueReader<'a>>,
+    },
+    EmptyMap,
+    Object(Vec<(&'a str, ValueReader<'a>)>),
+    Tuple(Vec<ValueReader<'a>>),
+    Nullable {
+        opts: IntoIter<bool>,
+        values: Box<ValueReader<'a>>,
+    },
+    Enum {
+        discriminants: IntoIter<u64>,
+        variants: Vec<(&'a str, ValueReader<'a>)>,
+    },
+    Rle {
+        runs: IntoIter<u64>,
+        values: Box<ValueReader<'a>>,
+        // The remaining repetitions of the current value
+        current: Option<(u64, Value)>,
+    },
+    Dictionary {
+        indices: IntoIter<u64>,
+        values: Box<ValueReader<'a>>,
+        cache: Vec<Value>,
+    },
+}
+
+#[cfg(feature = "read")]
+impl<'a> ValueReader<'a> {
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        let boxed = |sticks: Box<DynArrayBranch<'a>>| -> ReadResult<Box<Self>> { Ok(Box::new(Self::new(*sticks, options)?)) };
+        let limits = Limits::new(options);
+
+        let reader = match sticks {
+            DynArrayBranch::Void => ValueReader::Null,
+            DynArrayBranch::Boolean(_) => ValueReader::Bool(InfallibleReaderArray::new_infallible(sticks, options)?),
+            DynArrayBranch::Zigzag { .. } => ValueReader::Integer(InfallibleReaderArray::new_infallible(sticks, options)?),
+            DynArrayBranch::Integer(_) | DynArrayBranch::Delta { .. } | DynArrayBranch::U128 { .. } => {
+                ValueReader::UInteger(InfallibleReaderArray::new_infallible(sticks, options)?)
+            }
+            DynArrayBranch::Float(_) => ValueReader::Float(InfallibleReaderArray::new_infallible(sticks, options)?),
+            DynArrayBranch::Decimal { .. } => ValueReader::Decimal(InfallibleReaderArray::new_infallible(sticks, options)?),
+            DynArrayBranch::String(_) => ValueReader::String(InfallibleReaderArray::new_infallible(sticks, options)?),
+            DynArrayBranch::Blob { .. } => ValueReader::Blob(InfallibleReaderArray::new_infallible(sticks, options)?),
+            DynArrayBranch::Array0 => ValueReader::EmptyArray,
+            DynArrayBranch::Array { len, values } => {
+                let len: IntoIter<u64> = InfallibleReaderArray::new_infallible(*len, options)?;
+                limits.collections::<Value>(len.as_slice())?;
+                ValueReader::Array {
+                    len: FixedOrVariableLength::Variable(len),
+                    values: boxed(values)?,
+                }
+            }
+            DynArrayBranch::ArrayFixed { len, values } => ValueReader::Array {
+                len: FixedOrVariableLength::Fixed(limits.collection::<Value>(len)?),
+                values: boxed(values)?,
+            },
+            DynArrayBranch::Map0 => ValueReader::EmptyMap,
+            DynArrayBranch::Map { len, keys, values } => {
+                let len: IntoIter<u64> = InfallibleReaderArray::new_infallible(*len, options)?;
+                limits.collections::<(Value, Value)>(len.as_slice())?;
+                ValueReader::Map {
+                    len,
+                    keys: boxed(keys)?,
+                    values: boxed(values)?,
+                }
+            }
+            DynArrayBranch::Object { fields } => {
+                let fields: ReadResult<Vec<_>> = fields.into_iter().map(|(name, field)| Ok((name, Self::new(field, options)?))).collect();
+                ValueReader::Object(fields?)
+            }
+            DynArrayBranch::Tuple { fields } => {
+                let fields: ReadResult<Vec<_>> = fields.into_iter().map(|field| Self::new(field, options)).collect();
+                ValueReader::Tuple(fields?)
+            }
+            DynArrayBranch::Nullable { opt, values } => ValueReader::Nullable {
+                opts: InfallibleReaderArray::new_infallible(*opt, options)?,
+                values: boxed(values)?,
+            },
+            DynArrayBranch::Enum { discriminants, variants } => {
+                let variants: ReadResult<Vec<_>> = variants
+                    .into_iter()
+                    .map(|ArrayEnumVariant { ident, data }| Ok((ident, Self::new(data, options)?)))
+                    .collect();
+                ValueReader::Enum {
+                    discriminants: InfallibleReaderArray::new_infallible(*discriminants, options)?,
+                    variants: variants?,
+                }
+            }
+            DynArrayBranch::RLE { runs, values } => ValueReader::Rle {
+                runs: InfallibleReaderArray::new_infallible(*runs, options)?,
+                values: boxed(values)?,
+                current: None,
+            },
+            DynArrayBranch::Dictionary { indices, values } => ValueReader::Dictionary {
+                indices: InfallibleReaderArray::new_infallible(*indices, options)?,
+                values: boxed(values)?,
+                cache: Vec::new(),
+            },
+        };
+        Ok(reader)
+    }
+
+    fn read_next(&mut self) -> ReadResult<Value> {
+        let value = match self {
+            ValueReader::Null => Value::Null,
+            ValueReader::Bool(values) => Value::Bool(values.read_next_infallible()),
+            ValueReader::Integer(values) => Value::Integer(values.read_next_infallible()),
+            ValueReader::UInteger(values) => uinteger(values.read_next_infallible()),
+            ValueReader::Float(values) => Value::Float(values.read_next_infallible()),
+            ValueReader::Decimal(values) => Value::Decimal(values.read_next_infallible()),
+            ValueReader::String(values) => Value::String(values.read_next_infallible().to_owned()),
+            ValueReader::Blob(values) => Value::Blob(values.read_next_infallible().0),
+            ValueReader::Array { len, values } => {
+                let len = len.next();
+                let mut result = try_vec_with_capacity(len)?;
+                for _ in 0..len {
+                    result.push(values.read_next()?);
+                }
+                Value::Array(result)
+            }
+            ValueReader::EmptyArray => Value::Array(Vec::new()),
+            ValueReader::Map { len, keys, values } => {
+                let len = len.read_next_infallible();
+                let mut result = Vec::new();
+                for _ in 0..len {
+                    result.push((keys.read_next()?, values.read_next()?));
+                }
+                Value::Map(result)
+            }
+            ValueReader::EmptyMap => Value::Map(Vec::new()),
+            ValueReader::Object(fields) => {
+                let mut result = BTreeMap::new();
+                for (name, field) in fields.iter_mut() {
+                    result.insert((*name).to_owned(), field.read_next()?);
+                }
+                Value::Object(result)
+            }
+            ValueReader::Tuple(fields) => {
+                let fields: ReadResult<Vec<_>> = fields.iter_mut().map(|f| f.read_next()).collect();
+                Value::Array(fields?)
+            }
+            ValueReader::Nullable { opts, values } => {
+                if opts.read_next_infallible() {
+                    values.read_next()?
+                } else {
+                    Value::Null
+                }
+            }
+            ValueReader::Enum { discriminants, variants } => {
+                let discriminant = discriminants.read_next_infallible() as usize;
+                let (name, variant) = variants.get_mut(discriminant).ok_or(ReadError::InvalidFormat)?;
+                Value::Enum {
+                    discriminant: (*name).to_owned(),
+                    value: Box::new(variant.read_next()?),
+                }
+            }
+            // See also RleIterator
+            ValueReader::Rle { runs, values, current } => match current {
+                Some((remaining, value)) if *remaining != 0 => {
+                    *remaining -= 1;
+                    value.clone()
+                }
+                _ => {
+                    let run = runs.read_next_infallible();
+                    let value = values.read_next()?;
+                    *current = Some((run, value.clone()));
+                    value
+                }
+            },
+            // See also DictionaryIterator
+            ValueReader::Dictionary { indices, values, cache } => {
+                // Values are written in the order they first appear,
+                // so an index is never more than one past the cache.
+                let index = indices.read_next_infallible() as usize;
+                if index == cache.len() {
+                    cache.push(values.read_next()?);
+                }
+                cache.get(index).ok_or(ReadError::InvalidFormat)?.clone()
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "read")]
+impl<'a> ReaderArray<'a> for ValueReaderArray<'a> {
+    type Read = Value;
+    type Error = ReadError;
+    fn new(sticks: DynArrayBranch<'a>, options: &impl DecodeOptions) -> ReadResult<Self> {
+        profile!("ReaderArray::new");
+        Ok(ValueReaderArray(ValueReader::new(sticks, options)?))
+    }
+    fn read_next(&mut self) -> Result<Self::Read, Self::Error> {
+        self.0.read_next()
+    }
+}
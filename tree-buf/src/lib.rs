@@ -28,6 +28,9 @@ pub mod internal;
 
 pub mod experimental;
 
+#[cfg(feature = "serde")]
+pub mod serde;
+
 pub mod prelude {
     // Likely the minimum API that should go here. It's easier to add later than to remove.
 
@@ -56,6 +59,9 @@ pub mod prelude {
 #[cfg(feature = "read")]
 pub use internal::error::ReadError;
 
+#[cfg(feature = "diagnostics")]
+pub use internal::error::{ErrorContext, PathSegment};
+
 #[cfg(feature = "read")]
 pub use internal::Readable;
 
@@ -67,8 +73,23 @@ pub use internal::options;
 
 pub use crate::prelude::*;
 
+pub use internal::header::{FORMAT_VERSION, MIN_FORMAT_VERSION};
+
+#[cfg(feature = "read")]
+pub use internal::limits::Limit;
+
+pub use internal::Blob;
+
+pub use internal::Decimal;
+
 pub use internal::Ignore;
 
+pub use internal::Value;
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
 pub fn write<T: Writable>(value: &T) -> Vec<u8> {
     let options = EncodeOptionsDefault;
     write_with_options(value, &options)
@@ -77,35 +98,107 @@ pub fn write<T: Writable>(value: &T) -> Vec<u8> {
 #[cfg(feature = "write")]
 pub fn write_with_options<T: Writable>(value: &T, options: &impl EncodeOptions) -> Vec<u8> {
     profile!(T, "write_with_options");
-    use internal::encodings::varint::encode_suffix_varint;
 
+    let (mut bytes, lens) = write_unterminated(value, options);
+    write_lens(&lens, &mut bytes);
+    bytes
+}
+
+/// Writes the value to a stream, eg: a file or socket.
+/// The whole document is buffered in memory before anything is written to the stream,
+/// since type ids and lengths are only known once the value has been written.
+#[cfg(feature = "write")]
+pub fn write_to<T: Writable>(value: &T, writer: impl Write) -> io::Result<()> {
+    let options = EncodeOptionsDefault;
+    write_to_with_options(value, writer, &options)
+}
+
+#[cfg(feature = "write")]
+pub fn write_to_with_options<T: Writable>(value: &T, mut writer: impl Write, options: &impl EncodeOptions) -> io::Result<()> {
+    profile!(T, "write_to_with_options");
+
+    // The lens are only known after the whole value has been written, and must be
+    // written in reverse after the data. So, the data is buffered and then written
+    // to the stream followed by the lens.
+    let (bytes, lens) = write_unterminated(value, options);
+    writer.write_all(&bytes)?;
+    drop(bytes);
+
+    let mut suffix = Vec::new();
+    write_lens(&lens, &mut suffix);
+    writer.write_all(&suffix)?;
+    writer.flush()
+}
+
+#[cfg(feature = "write")]
+pub fn write_to_file<T: Writable>(value: &T, path: impl AsRef<Path>) -> io::Result<()> {
+    let file = File::create(path)?;
+    write_to(value, BufWriter::new(file))
+}
+
+/// Writes the data for a value, returning it along with the lens which have not yet been appended.
+#[cfg(feature = "write")]
+fn write_unterminated<T: Writable>(value: &T, options: &impl EncodeOptions) -> (Vec<u8>, Vec<usize>) {
     let mut lens = Vec::new();
     let mut bytes = Vec::new();
+    write_header(options, &mut bytes);
     let mut stream = WriterStream::new(&mut bytes, &mut lens, options);
     stream.write_with_id(|stream| T::write_root(value, stream));
+    (bytes, lens)
+}
+
+#[cfg(feature = "write")]
+fn write_lens(lens: &[usize], bytes: &mut Vec<u8>) {
+    use internal::encodings::varint::encode_suffix_varint;
 
     for len in lens.iter().rev() {
-        encode_suffix_varint(*len as u64, &mut bytes);
+        encode_suffix_varint(*len as u64, bytes);
     }
-
-    bytes
 }
 
 #[cfg(feature = "read")]
-pub fn read<T: Readable>(bytes: &[u8]) -> ReadResult<T> {
+pub fn read<'a, T: Readable<'a>>(bytes: &'a [u8]) -> ReadResult<T> {
     let options = DecodeOptionsDefault;
     read_with_options(bytes, &options)
 }
 
 #[cfg(feature = "read")]
-pub fn read_with_options<T: Readable>(bytes: &[u8], options: &impl DecodeOptions) -> ReadResult<T> {
+pub fn read_with_options<'a, T: Readable<'a>>(bytes: &'a [u8], options: &impl DecodeOptions) -> ReadResult<T> {
     profile!(T, "read_with_options");
-    let sticks = read_root(bytes)?;
+    if options.require_header() && !has_header(bytes) {
+        return Err(ReadError::InvalidFormat);
+    }
+    let sticks = read_root(bytes, options)?;
     T::read(sticks, options)
 }
 
-// TODO: Figure out recursion, at least enough to handle this: https://docs.rs/serde_json/1.0.44/serde_json/value/enum.Value.html
-// TODO: Nullable should be able to handle recursion as well, even if Option doesn't. (Option<Box<T>> could though)
+/// Reads a value from a stream, eg: a file or socket.
+/// The lens are stored at the end of the data, so the stream is read to completion before decoding.
+/// Errors in the data are reported with `io::ErrorKind::InvalidData`.
+/// Since the bytes do not outlive this call, `T` may not borrow from them.
+#[cfg(feature = "read")]
+pub fn read_from<T: for<'a> Readable<'a>>(reader: impl Read) -> io::Result<T> {
+    let options = DecodeOptionsDefault;
+    read_from_with_options(reader, &options)
+}
+
+#[cfg(feature = "read")]
+pub fn read_from_with_options<T: for<'a> Readable<'a>>(mut reader: impl Read, options: &impl DecodeOptions) -> io::Result<T> {
+    profile!(T, "read_from_with_options");
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    read_with_options(&bytes, options).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(feature = "read")]
+pub fn read_from_file<T: for<'a> Readable<'a>>(path: impl AsRef<Path>) -> io::Result<T> {
+    let file = File::open(path)?;
+    read_from(BufReader::new(file))
+}
+
+// Recursive types (eg: struct Node { children: Vec<Node> }) are supported by boxing the
+// nested WriterArray and ReaderArray of Vec, Option, HashMap and Box. Each depth of
+// recursion is written as its own branch, so the schema grows with the depth of the data.
 
 // See also: c94adae3-9778-4a42-a454-650a97a87483
 // TODO: (Performance) When recursion is not involved, there is a maximum to the amount of schema info needed to write
@@ -131,7 +224,4 @@ pub fn read_with_options<T: Readable>(bytes: &[u8], options: &impl DecodeOptions
 // TODO: Automatic type extraction for json:
 // http://stevehanov.ca/blog/?id=104
 
-// TODO: Add decimal type
-// This seems a reasonable starting point: https://github.com/paupino/rust-decimal
-//
 // TODO: Consider RLE encoding and Dictionary encoding as pre-processors
@@ -0,0 +1,200 @@
+use super::Error;
+use crate::Value;
+use ::serde::de::value::{MapDeserializer, SeqDeserializer};
+use ::serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use ::serde::forward_to_deserialize_any;
+use std::convert::TryFrom;
+
+/// Reads any `Deserialize` type from a [`Value`](crate::Value).
+pub struct Deserializer {
+    value: Value,
+}
+
+impl Deserializer {
+    pub fn new(value: Value) -> Self {
+        Self { value }
+    }
+
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::new(crate::read(bytes)?))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Deserializer {
+    type Deserializer = Self;
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+// Allows the value adapters from serde to be used for arrays and maps.
+struct Item(Value);
+
+impl<'de> IntoDeserializer<'de, Error> for Item {
+    type Deserializer = Deserializer;
+    fn into_deserializer(self) -> Deserializer {
+        Deserializer::new(self.0)
+    }
+}
+
+fn visit_array<'de, V: Visitor<'de>>(values: Vec<Value>, visitor: V) -> Result<V::Value, Error> {
+    let mut seq = SeqDeserializer::new(values.into_iter().map(Item));
+    let result = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(result)
+}
+
+fn visit_map<'de, V: Visitor<'de>>(entries: impl Iterator<Item = (Value, Value)>, visitor: V) -> Result<V::Value, Error> {
+    let mut map = MapDeserializer::new(entries.map(|(k, v)| (Item(k), Item(v))));
+    let result = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(result)
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::Integer(v) => {
+                if let Ok(v) = u64::try_from(v) {
+                    visitor.visit_u64(v)
+                } else if let Ok(v) = i64::try_from(v) {
+                    visitor.visit_i64(v)
+                } else {
+                    visitor.visit_i128(v)
+                }
+            }
+            Value::UInteger(v) => visitor.visit_u128(v),
+            Value::Float(v) => visitor.visit_f64(v),
+            // Eg: rust_decimal reads from a string, which does not lose precision
+            Value::Decimal(v) => visitor.visit_string(v.to_string()),
+            Value::String(v) => visitor.visit_string(v),
+            Value::Blob(v) => visitor.visit_byte_buf(v),
+            Value::Array(values) => visit_array(values, visitor),
+            Value::Object(fields) => visit_map(fields.into_iter().map(|(k, v)| (Value::String(k), v)), visitor),
+            Value::Map(entries) => visit_map(entries.into_iter(), visitor),
+            Value::Enum { discriminant, value } => visitor.visit_enum(EnumDeserializer { discriminant, value: *value }),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Enum { discriminant, value } => visitor.visit_enum(EnumDeserializer { discriminant, value: *value }),
+            // Unit variants written by other means may be only the name
+            Value::String(discriminant) => visitor.visit_enum(EnumDeserializer { discriminant, value: Value::Null }),
+            _ => Err(de::Error::invalid_type(unexpected(&self.value), &"enum")),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // Integers are accepted for floats, since the format may store eg: 1.0 as an integer.
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Integer(v) => visitor.visit_f64(v as f64),
+            Value::UInteger(v) => visitor.visit_f64(v as f64),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Array(values) => {
+                let bytes: Option<Vec<u8>> = values
+                    .iter()
+                    .map(|v| match v {
+                        Value::Integer(i) => u8::try_from(*i).ok(),
+                        _ => None,
+                    })
+                    .collect();
+                match bytes {
+                    Some(bytes) => visitor.visit_byte_buf(bytes),
+                    None => visit_array(values, visitor),
+                }
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    // Blobs are accepted for sequences, so that eg: a Vec<u8> can be read from a Blob.
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Blob(bytes) => visit_array(bytes.into_iter().map(|b| Value::Integer(b.into())).collect(), visitor),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string
+        unit unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+fn unexpected(value: &Value) -> de::Unexpected<'_> {
+    match value {
+        Value::Null => de::Unexpected::Unit,
+        Value::Bool(v) => de::Unexpected::Bool(*v),
+        Value::Integer(_) | Value::UInteger(_) => de::Unexpected::Other("integer"),
+        Value::Float(v) => de::Unexpected::Float(*v),
+        Value::Decimal(_) => de::Unexpected::Other("decimal"),
+        Value::String(v) => de::Unexpected::Str(v),
+        Value::Blob(v) => de::Unexpected::Bytes(v),
+        Value::Array(_) => de::Unexpected::Seq,
+        Value::Object(_) | Value::Map(_) => de::Unexpected::Map,
+        Value::Enum { .. } => de::Unexpected::Enum,
+    }
+}
+
+struct EnumDeserializer {
+    discriminant: String,
+    value: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+    type Variant = Deserializer;
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Deserializer), Error> {
+        let variant = seed.deserialize(Deserializer::new(Value::String(self.discriminant)))?;
+        Ok((variant, Deserializer::new(self.value)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = Error;
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+}
@@ -0,0 +1,83 @@
+//! A bridge for types which implement serde's `Serialize` and `Deserialize`,
+//! for when deriving tree-buf's `Write` and `Read` is not possible. Eg: for third-party types.
+//!
+//! Data is converted to and from a [`Value`](crate::Value), so it benefits from the same
+//! columnar compression. Serde's data model is mapped onto tree-buf as follows:
+//! * structs are objects
+//! * sequences, tuples and tuple structs are arrays
+//! * bytes (eg: from `serde_bytes`) are blobs
+//! * maps are maps, whatever the type of their keys
+//! * enum variants are enums, named by the variant
+//! * `None`, `()`, and unit structs are void
+//! * `Some` is the value it holds. So, writing `Some` of a void value (eg: `Some(())` or `Some(None)`) is an error
+//!
+//! The whole intermediate `Value` is held in memory alongside the data, and the document is
+//! only written once it is complete. Deriving `Write` and `Read` remains faster where possible.
+
+use crate::prelude::*;
+use std::fmt::{Debug, Display, Formatter};
+
+#[cfg(feature = "read")]
+mod de;
+#[cfg(feature = "write")]
+mod ser;
+
+#[cfg(feature = "read")]
+pub use de::Deserializer;
+#[cfg(feature = "write")]
+pub use ser::Serializer;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    #[cfg(feature = "read")]
+    Read(ReadError),
+    /// An error reported by the Serialize or Deserialize implementation,
+    /// or data which tree-buf cannot represent.
+    Message(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            #[cfg(feature = "read")]
+            Error::Read(inner) => Display::fmt(inner, f),
+            Error::Message(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(feature = "read")]
+impl From<ReadError> for Error {
+    fn from(value: ReadError) -> Self {
+        Error::Read(value)
+    }
+}
+
+#[cfg(feature = "write")]
+impl ::serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+#[cfg(feature = "read")]
+impl ::serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+#[cfg(feature = "write")]
+pub fn write<T: ::serde::Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    profile!(T, "serde::write");
+    let value = value.serialize(Serializer)?;
+    Ok(crate::write(&value))
+}
+
+#[cfg(feature = "read")]
+pub fn read<T: ::serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    profile!(T, "serde::read");
+    T::deserialize(Deserializer::from_slice(bytes)?)
+}
@@ -0,0 +1,120 @@
+use tree_buf::internal::encodings::varint::encode_prefix_varint;
+use tree_buf::internal::RootTypeId;
+use tree_buf::prelude::*;
+mod common;
+use common::*;
+use tree_buf::{decode_options, encode_options, options, read_with_options, write_with_options, Blob, Limit, ReadError, Value};
+
+// Not compressible, so that the bytes are written as they are
+fn noise(len: usize) -> Blob {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let bytes = (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect::<Vec<_>>();
+    Blob(bytes)
+}
+
+fn repetitive(len: usize) -> Blob {
+    Blob(b"tree-buf ".iter().copied().cycle().take(len).collect())
+}
+
+#[test]
+fn empty() {
+    round_trip(&Blob::default(), 2, 7);
+}
+
+#[test]
+fn small() {
+    round_trip(&Blob(vec![1, 2, 3]), 5, 14);
+}
+
+#[test]
+fn incompressible() {
+    let blob = noise(1000);
+    round_trip(&blob, 1003, 1031);
+    let bytes = write(&blob);
+    assert_eq!(bytes[0], u8::from(RootTypeId::Blob));
+}
+
+#[test]
+fn compressible() {
+    let blob = repetitive(1000);
+    round_trip(&blob, 27, 45);
+    let bytes = write(&blob);
+    assert_eq!(bytes[0], u8::from(RootTypeId::BlobLz4));
+}
+
+#[test]
+fn disable_compression() {
+    let blob = repetitive(1000);
+    let options = encode_options! { options::DisableBlobCompression };
+    let bytes = write_with_options(&blob, &options);
+    assert_eq!(bytes.len(), 1003);
+    assert_eq!(read::<Blob>(&bytes), Ok(blob));
+}
+
+#[test]
+fn mixed_array() {
+    let blobs = vec![repetitive(100), Blob::default(), noise(10), repetitive(3)];
+    serialize_eq(&blobs, &blobs, 38);
+}
+
+// Much smaller than the same bytes written as integers
+#[test]
+fn smaller_than_vec() {
+    let blob = noise(1000);
+    let as_vec = write(&blob.0).len();
+    let as_blob = write(&blob).len();
+    assert!(as_blob < as_vec, "{} >= {}", as_blob, as_vec);
+}
+
+// Version 2 of the format has no blobs, so the bytes are written as a Vec<u8> instead.
+#[test]
+fn older_format_version() {
+    let options = encode_options! { options::TargetFormatVersion(2) };
+    for blob in vec![repetitive(100), noise(1), Blob::default()] {
+        let bytes = write_with_options(&blob, &options);
+        assert_eq!(read::<Vec<u8>>(&bytes), Ok(blob.0.clone()));
+        assert_eq!(read::<Blob>(&bytes), Ok(blob));
+    }
+    for blobs in vec![vec![repetitive(100), Blob::default(), noise(10)], vec![noise(3), repetitive(3)], vec![Blob::default(), Blob::default()]] {
+        let bytes = write_with_options(&blobs, &options);
+        assert_eq!(read::<Vec<Vec<u8>>>(&bytes), Ok(blobs.iter().map(|b| b.0.clone()).collect()));
+        assert_eq!(read::<Vec<Blob>>(&bytes), Ok(blobs));
+    }
+}
+
+#[test]
+fn value() {
+    round_trip(&Value::Blob(repetitive(100).0), 23, 37);
+    let blobs = vec![Value::Blob(vec![1, 2]), Value::Null, Value::Blob(vec![3])];
+    serialize_eq(&blobs, &blobs, 15);
+    serialize_eq(&repetitive(100), &Value::Blob(repetitive(100).0), 23);
+}
+
+#[test]
+fn hostile_decompressed_length() {
+    let mut bytes = vec![u8::from(RootTypeId::BlobLz4)];
+    encode_prefix_varint(1 << 40, &mut bytes);
+    encode_prefix_varint(1, &mut bytes);
+    bytes.push(0);
+    let options = decode_options! { options::MaxAllocation(1 << 20) };
+    match read_with_options::<Blob>(&bytes, &options).unwrap_err().kind() {
+        ReadError::LimitExceeded(Limit::Allocation) => {}
+        other => panic!("Expected the allocation limit to be exceeded, but got {:?}", other),
+    }
+}
+
+#[test]
+fn corrupt_compressed_bytes() {
+    let mut bytes = vec![u8::from(RootTypeId::BlobLz4)];
+    encode_prefix_varint(100, &mut bytes);
+    encode_prefix_varint(3, &mut bytes);
+    bytes.extend_from_slice(&[0xff, 0xff, 0xff]);
+    assert_eq!(read::<Blob>(&bytes).unwrap_err().kind(), &ReadError::InvalidFormat);
+}
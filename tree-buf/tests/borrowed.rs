@@ -0,0 +1,95 @@
+use std::borrow::Cow;
+use tree_buf::prelude::*;
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct LogLine<'a> {
+    level: &'a str,
+    message: Cow<'a, str>,
+    payload: &'a [u8],
+    count: u64,
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct OwnedLogLine {
+    level: String,
+    message: String,
+    payload: Vec<u8>,
+    count: u64,
+}
+
+fn is_borrowed_from(s: &[u8], bytes: &[u8]) -> bool {
+    let range = bytes.as_ptr_range();
+    range.contains(&s.as_ptr())
+}
+
+#[test]
+fn borrowed_str_root() {
+    let bytes = write(&"borrowed string");
+    let s: &str = read(&bytes).unwrap();
+    assert_eq!(s, "borrowed string");
+    assert!(is_borrowed_from(s.as_bytes(), &bytes));
+}
+
+#[test]
+fn borrowed_bytes_root() {
+    for len in 0..4 {
+        let data: Vec<u8> = (0..len).collect();
+        let bytes = write(&&data[..]);
+        let parsed: &[u8] = read(&bytes).unwrap();
+        assert_eq!(parsed, &data[..]);
+    }
+}
+
+#[test]
+fn borrowed_struct_array() {
+    let lines = (0..50u8)
+        .map(|i| LogLine {
+            level: if i % 3 == 0 { "warn" } else { "info" },
+            message: Cow::Owned(format!("message {}", i)),
+            payload: if i % 2 == 0 { &[1, 2, 3] } else { &[4] },
+            count: i as u64,
+        })
+        .collect::<Vec<_>>();
+    let bytes = write(&lines);
+    let parsed: Vec<LogLine> = read(&bytes).unwrap();
+    assert_eq!(lines, parsed);
+    for line in parsed.iter() {
+        assert!(matches!(line.message, Cow::Borrowed(_)));
+        assert!(is_borrowed_from(line.message.as_bytes(), &bytes));
+        assert!(is_borrowed_from(line.payload, &bytes));
+    }
+}
+
+#[test]
+fn borrowed_to_owned() {
+    let line = LogLine {
+        level: "error",
+        message: Cow::Borrowed("disk full"),
+        payload: &[0, 1, 2, 3, 4],
+        count: 1,
+    };
+    let bytes = write(&vec![line.clone(), line]);
+    let parsed: Vec<OwnedLogLine> = read(&bytes).unwrap();
+    assert_eq!(parsed[1].level, "error");
+    assert_eq!(parsed[1].message, "disk full");
+    assert_eq!(parsed[1].payload, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn owned_to_borrowed() {
+    let line = OwnedLogLine {
+        level: "error".to_owned(),
+        message: "disk full".to_owned(),
+        payload: vec![],
+        count: 1,
+    };
+    let bytes = write(&line);
+    let parsed: LogLine = read(&bytes).unwrap();
+    assert_eq!(parsed.message, "disk full");
+}
+
+#[test]
+fn compressed_bytes_cannot_be_borrowed() {
+    let bytes = write(&vec![0u8, 1, 1, 0]);
+    assert_eq!(read::<&[u8]>(&bytes).unwrap_err().kind(), &tree_buf::ReadError::SchemaMismatch);
+}
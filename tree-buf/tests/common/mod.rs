@@ -9,21 +9,17 @@ use tree_buf::{Readable, Writable};
 /// If we add compression and achieve lower, we can ratchet the number down.
 /// This ensures the use of the format is improving.
 /// Works on both arrays and root values to hit both code paths.
-pub fn round_trip<T: Writable + Readable + Clone + std::fmt::Debug + PartialEq + 'static>(value: &T, root_size: impl Into<Option<i32>>, array_size: impl Into<Option<i32>>)
-// Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
-where
-    tree_buf::ReadError: From<<<T as Readable>::ReaderArray as tree_buf::internal::ReaderArray>::Error>,
-{
+pub fn round_trip<T: Writable + for<'a> Readable<'a> + Clone + std::fmt::Debug + PartialEq + 'static>(
+    value: &T,
+    root_size: impl Into<Option<i32>>,
+    array_size: impl Into<Option<i32>>,
+) {
     serialize_eq(value, value, root_size);
     let v = vec![value.clone(), value.clone()];
     serialize_eq(&v, &v, array_size);
 }
 
-pub fn serialize_eq<I: Writable, O: Readable + Debug + PartialEq>(i: &I, o: &O, size: impl Into<Option<i32>>)
-// Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
-where
-    tree_buf::ReadError: From<<<O as Readable>::ReaderArray as tree_buf::internal::ReaderArray>::Error>,
-{
+pub fn serialize_eq<I: Writable, O: for<'a> Readable<'a> + Debug + PartialEq>(i: &I, o: &O, size: impl Into<Option<i32>>) {
     let bytes = write(i);
     let result = read(&bytes);
     //dbg!(tree_buf::internal::read_root(&bytes));
@@ -36,11 +32,7 @@ where
     }
 }
 
-pub fn round_trip_default<T: Default + Readable + Writable + Debug + PartialEq + Clone + 'static>(root_size: i32, array_size: i32)
-// Overly verbose because of `?` requiring `From` See also ec4fa3ba-def5-44eb-9065-e80b59530af6
-where
-    tree_buf::ReadError: From<<<T as Readable>::ReaderArray as tree_buf::internal::ReaderArray>::Error>,
-{
+pub fn round_trip_default<T: Default + for<'a> Readable<'a> + Writable + Debug + PartialEq + Clone + 'static>(root_size: i32, array_size: i32) {
     let data = T::default();
     round_trip(&data, root_size, array_size);
 }
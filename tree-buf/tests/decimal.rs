@@ -0,0 +1,127 @@
+use tree_buf::prelude::*;
+mod common;
+use common::*;
+use tree_buf::{encode_options, options, write_with_options, Decimal, Value};
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Listing {
+    pub id: u64,
+    pub price: Decimal,
+}
+
+#[test]
+fn round_trips() {
+    round_trip_default::<Decimal>(3, 9);
+    round_trip(&Decimal::new(125, 2), 4, 11);
+    round_trip(&Decimal::new(-125, 2), 4, 12);
+    round_trip(&Decimal::new(i128::MIN, u32::MAX), 23, 26);
+    round_trip(&Decimal::new(89_000_000_000_000_000_000, 18), 19, 25);
+}
+
+// The scale column compresses well when all prices have the same number of decimal places
+#[test]
+fn prices() {
+    let listings = (0..100u64)
+        .map(|id| Listing {
+            id,
+            price: Decimal::new(1999 + id as i128 * 100, 2),
+        })
+        .collect::<Vec<_>>();
+    serialize_eq(&listings, &listings, 126);
+
+    let as_strings = listings.iter().map(|l| (l.id, l.price.to_string())).collect::<Vec<_>>();
+    assert!(write(&listings).len() < write(&as_strings).len());
+}
+
+#[test]
+fn integers_as_decimals() {
+    serialize_eq(&5u32, &Decimal::new(5, 0), 2);
+    serialize_eq(&-5i64, &Decimal::new(-5, 0), 2);
+    serialize_eq(&vec![1u64, 2, 300], &vec![Decimal::new(1, 0), Decimal::new(2, 0), Decimal::new(300, 0)], 8);
+    serialize_eq(&vec![-1i64, 2], &vec![Decimal::new(-1, 0), Decimal::new(2, 0)], 7);
+}
+
+#[test]
+fn schema_mismatch() {
+    let bytes = write(&"1.25".to_owned());
+    assert!(read::<Decimal>(&bytes).is_err());
+    let bytes = write(&vec![1.5f64, 2.5]);
+    assert!(read::<Vec<Decimal>>(&bytes).is_err());
+}
+
+#[test]
+fn display() {
+    assert_eq!(Decimal::new(125, 2).to_string(), "1.25");
+    assert_eq!(Decimal::new(-125, 2).to_string(), "-1.25");
+    assert_eq!(Decimal::new(5, 3).to_string(), "0.005");
+    assert_eq!(Decimal::new(-5, 1).to_string(), "-0.5");
+    assert_eq!(Decimal::new(100, 0).to_string(), "100");
+    assert_eq!(Decimal::new(0, 2).to_string(), "0.00");
+}
+
+#[test]
+fn value() {
+    round_trip(&Value::Decimal(Decimal::new(125, 2)), 4, 11);
+    let values = vec![Value::Decimal(Decimal::new(1, 1)), Value::Null, Value::Decimal(Decimal::new(22, 2))];
+    serialize_eq(&values, &values, 15);
+}
+
+// Version 3 of the format has no decimals, so the mantissa and scale are written as a tuple instead.
+#[test]
+fn older_format_version() {
+    let options = encode_options! { options::TargetFormatVersion(3) };
+    let bytes = write_with_options(&Decimal::new(-125, 2), &options);
+    assert_eq!(read::<(i128, u32)>(&bytes), Ok((-125, 2)));
+    assert_eq!(read::<Decimal>(&bytes), Ok(Decimal::new(-125, 2)));
+    let data = vec![Decimal::new(1999, 2), Decimal::new(i128::MIN, 0), Decimal::new(5, 3)];
+    let bytes = write_with_options(&data, &options);
+    assert_eq!(read::<Vec<(i128, u32)>>(&bytes), Ok(data.iter().map(|d| (d.mantissa, d.scale)).collect()));
+    assert_eq!(read::<Vec<Decimal>>(&bytes), Ok(data));
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_feature {
+    use super::*;
+    use rust_decimal::Decimal as RustDecimal;
+    use std::str::FromStr;
+
+    fn dec(s: &str) -> RustDecimal {
+        RustDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(&dec("1.25"), 4, 11);
+        round_trip(&dec("-79228162514264337593543950335"), 19, 23);
+        round_trip(&dec("0.0000000000000000000000000001"), 3, 10);
+        round_trip(&RustDecimal::default(), 3, 9);
+    }
+
+    #[test]
+    fn preserves_scale() {
+        let bytes = write(&dec("1.50"));
+        assert_eq!(read::<RustDecimal>(&bytes).unwrap().scale(), 2);
+    }
+
+    #[test]
+    fn interchangeable() {
+        serialize_eq(&dec("1.25"), &Decimal::new(125, 2), 4);
+        serialize_eq(&vec![Decimal::new(-125, 2)], &vec![dec("-1.25")], 5);
+    }
+
+    #[test]
+    fn older_format_version() {
+        let options = encode_options! { options::TargetFormatVersion(3) };
+        assert_eq!(read::<RustDecimal>(&write_with_options(&dec("1.25"), &options)), Ok(dec("1.25")));
+        let data = vec![dec("-1.25"), dec("0.001")];
+        assert_eq!(read::<Vec<RustDecimal>>(&write_with_options(&data, &options)), Ok(data));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let bytes = write(&Decimal::new(1, 29));
+        assert!(read::<RustDecimal>(&bytes).is_err());
+        let bytes = write(&vec![Decimal::new(1, 0), Decimal::new(i128::MAX, 0)]);
+        assert!(read::<Vec<RustDecimal>>(&bytes).is_err());
+    }
+}
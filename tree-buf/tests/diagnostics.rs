@@ -0,0 +1,100 @@
+use std::any::type_name;
+use tree_buf::prelude::*;
+use tree_buf::ReadError;
+
+mod before {
+    use tree_buf::prelude::*;
+
+    #[derive(Write)]
+    pub struct Collection {
+        pub features: Vec<Feature>,
+    }
+
+    #[derive(Write)]
+    pub struct Feature {
+        pub geometry: Geometry,
+    }
+
+    #[derive(Write)]
+    pub struct Geometry {
+        pub coordinates: String,
+    }
+}
+
+#[derive(Read, Debug)]
+#[allow(dead_code)]
+pub struct Collection {
+    features: Vec<Feature>,
+}
+
+#[derive(Read, Debug)]
+#[allow(dead_code)]
+pub struct Feature {
+    geometry: Geometry,
+}
+
+#[derive(Read, Debug)]
+#[allow(dead_code)]
+pub struct Geometry {
+    coordinates: Vec<f64>,
+}
+
+#[test]
+fn path_to_mismatched_field() {
+    let feature = || before::Feature {
+        geometry: before::Geometry { coordinates: "0, 0".to_owned() },
+    };
+    let bytes = write(&before::Collection {
+        features: vec![feature(), feature()],
+    });
+    let err = read::<Collection>(&bytes).unwrap_err();
+    assert_eq!(err.kind(), &ReadError::SchemaMismatch);
+
+    let context = err.context().unwrap();
+    assert_eq!(context.path(), "features[].geometry.coordinates");
+    assert_eq!(context.expected, Some(type_name::<Vec<f64>>()));
+    assert_eq!(context.found, Some("String"));
+    assert_eq!(context.offset, None);
+}
+
+#[test]
+fn tuple_index() {
+    let bytes = write(&(1u32, "two"));
+    let err = read::<(u32, u32)>(&bytes).unwrap_err();
+    let context = err.context().unwrap();
+    assert_eq!(context.path(), "1");
+    assert_eq!(context.expected, Some("u32"));
+    assert_eq!(context.found, Some("String"));
+}
+
+#[test]
+fn enum_variant() {
+    #[derive(Write)]
+    pub enum Before {
+        Point { x: u32 },
+    }
+    #[derive(Read, Debug)]
+    #[allow(dead_code)]
+    pub enum After {
+        Point { x: bool },
+    }
+    let bytes = write(&vec![Before::Point { x: 1 }, Before::Point { x: 2 }]);
+    let err = read::<Vec<After>>(&bytes).unwrap_err();
+    assert_eq!(err.context().unwrap().path(), "[].Point.x");
+}
+
+#[test]
+fn format_error_offset() {
+    let mut bytes = write(&"a string".to_owned());
+    bytes.truncate(bytes.len() - 1);
+    let err = read::<String>(&bytes).unwrap_err();
+    assert_eq!(err.kind(), &ReadError::InvalidFormat);
+    assert_eq!(err.context().unwrap().offset, Some(2));
+}
+
+#[test]
+fn display() {
+    let bytes = write(&(1u32, "two"));
+    let err = read::<(u32, u32)>(&bytes).unwrap_err();
+    assert_eq!(err.to_string(), "The expected schema did not match that in the document. At: 1 Expected: u32 Found: String");
+}
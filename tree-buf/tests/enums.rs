@@ -75,8 +75,6 @@ fn mixed_void_and_single() {
     round_trip(&vec![Mixed::Ex, Mixed::One(2), Mixed::One(2), Mixed::One(3), Mixed::Ex], 20, 24);
 }
 
-/*
-// TODO: Enable test
 #[test]
 fn wierd_unit_variants() {
     #[derive(Read, Write, Debug, PartialEq, Clone)]
@@ -87,20 +85,55 @@ fn wierd_unit_variants() {
 
     #[derive(Read, Write, Debug, PartialEq, Clone)]
     enum Named {
-        One{},
-        Two{},
+        One {},
+        Two {},
     }
 
+    round_trip(&Unnamed::Two(), 6, 12);
+    round_trip(&Named::Two {}, 6, 12);
+    round_trip(&vec![Unnamed::One(), Unnamed::Two(), Unnamed::One()], 17, 19);
 }
 
-// TODO: Enable test
 #[test]
 fn struct_value() {
-    #[derive(Read, Write, Debug, PartialEq)]
+    #[derive(Read, Write, Debug, PartialEq, Clone)]
     enum HasStruct {
         S { one: u32, two: u32 },
     }
 
-    round_trip(&HasStruct::S { one: 15, two: 15 }, 0, 0);
+    round_trip(&HasStruct::S { one: 15, two: 15 }, 16, 26);
+}
+
+#[test]
+fn multiple_unnamed_fields() {
+    #[derive(Read, Write, Debug, PartialEq, Clone)]
+    enum HasTuple {
+        T(u32, bool),
+    }
+
+    round_trip(&HasTuple::T(15, true), 7, 17);
+}
+
+#[test]
+fn mixed_variant_kinds() {
+    #[derive(Read, Write, Debug, PartialEq, Clone)]
+    enum Event {
+        Click { x: u32, y: u32 },
+        Key(u32, bool),
+        Scroll(i32),
+        Quit,
+    }
+
+    let events = vec![
+        Event::Click { x: 10, y: 20 },
+        Event::Key(65, true),
+        Event::Quit,
+        Event::Click { x: 11, y: 21 },
+        Event::Scroll(-3),
+        Event::Key(66, false),
+    ];
+
+    round_trip(&Event::Click { x: 10, y: 20 }, 16, 26);
+    round_trip(&Event::Key(65, true), 9, 19);
+    round_trip(&events, 58, 67);
 }
-*/
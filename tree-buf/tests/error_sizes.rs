@@ -7,6 +7,14 @@ use std::mem::size_of;
 pub fn tuples_reduce_error_size() {
     type T = (f64, f64);
     let orig = size_of::<T>();
-    let wrapped = size_of::<Result<T, <<T as ::tree_buf::internal::Readable>::ReaderArray as ::tree_buf::internal::ReaderArray>::Error>>();
+    let wrapped = size_of::<Result<T, <<T as ::tree_buf::internal::Readable<'static>>::ReaderArray as ::tree_buf::internal::ReaderArray<'static>>::Error>>();
     assert_eq!(orig, wrapped);
 }
+
+/// Context is only collected with the diagnostics feature, so that
+/// errors stay cheap to pass around by default.
+#[test]
+#[cfg(not(feature = "diagnostics"))]
+pub fn read_error_is_small() {
+    assert!(size_of::<::tree_buf::ReadError>() <= size_of::<u16>());
+}
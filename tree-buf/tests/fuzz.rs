@@ -0,0 +1,146 @@
+//! Reads mutated documents to check that corrupt or hostile input
+//! produces an error rather than a panic. The limits are set the way
+//! they should be for untrusted input, since otherwise a document may
+//! legitimately claim to hold billions of items.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use tree_buf::internal::read_root;
+use tree_buf::prelude::*;
+use tree_buf::{decode_options, options, read_with_options, Readable, Value, Writable};
+
+#[derive(Read, Write, Debug, PartialEq, Clone)]
+pub struct Record {
+    id: u64,
+    name: String,
+    score: f64,
+    ratio: f32,
+    flags: Vec<bool>,
+    tags: Option<Vec<String>>,
+    offsets: Vec<i32>,
+    shape: Shape,
+    pair: (u8, i64),
+    attributes: HashMap<String, u16>,
+}
+
+#[derive(Read, Write, Debug, PartialEq, Clone)]
+pub enum Shape {
+    Point,
+    Circle(f64),
+    Polygon { points: Vec<(f64, f64)> },
+}
+
+fn record(i: usize) -> Record {
+    let mut attributes = HashMap::new();
+    attributes.insert(format!("attr{}", i), i as u16);
+    Record {
+        id: i as u64 * 1000,
+        name: format!("record {}", i),
+        score: i as f64 * 1.5,
+        ratio: 0.25,
+        flags: vec![true, false, i & 1 == 0, true],
+        tags: if i == 0 { None } else { Some(vec!["a".to_owned(), "b".to_owned()]) },
+        offsets: vec![-1, 0, i as i32, 300_000],
+        shape: match i % 3 {
+            0 => Shape::Point,
+            1 => Shape::Circle(2.0),
+            _ => Shape::Polygon {
+                points: vec![(0.0, 0.0), (1.0, 0.5), (i as f64, 2.0)],
+            },
+        },
+        pair: (i as u8, -(i as i64)),
+        attributes,
+    }
+}
+
+fn mutate(rng: &mut StdRng, bytes: &[u8]) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    for _ in 0..rng.gen_range(1, 4) {
+        if bytes.is_empty() {
+            bytes.push(rng.gen());
+            continue;
+        }
+        let i = rng.gen_range(0, bytes.len());
+        match rng.gen_range(0, 6) {
+            0 => bytes[i] ^= 1 << rng.gen_range(0, 8),
+            1 => bytes[i] = rng.gen(),
+            2 => bytes[i] = *[0x00, 0x7F, 0x80, 0xFF].get(rng.gen_range(0, 4)).unwrap(),
+            3 => bytes.truncate(i),
+            4 => bytes.insert(i, rng.gen()),
+            _ => {
+                bytes.remove(i);
+            }
+        }
+    }
+    bytes
+}
+
+fn read_all<T: for<'a> Readable<'a>>(input: &[u8]) {
+    let options = decode_options! { options::MaxCollectionLength(10_000), options::MaxAllocation(1 << 20) };
+    let _ = read_root(input, &options);
+    let _ = read_with_options::<T>(input, &options);
+    let _ = read_with_options::<Value>(input, &options);
+}
+
+fn fuzz<T: Writable + for<'a> Readable<'a> + Debug>(seed: u64, value: &T) {
+    let bytes = write(value);
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..2000 {
+        let input = mutate(&mut rng, &bytes);
+        let result = catch_unwind(AssertUnwindSafe(|| read_all::<T>(&input)));
+        if result.is_err() {
+            panic!("Panicked while reading {:?}", input);
+        }
+    }
+}
+
+#[test]
+fn fuzz_record() {
+    fuzz(1, &record(1));
+}
+
+#[test]
+fn fuzz_records() {
+    fuzz(2, &(0..20).map(record).collect::<Vec<_>>());
+}
+
+#[test]
+fn fuzz_numbers() {
+    fuzz(3, &(0..100u64).map(|i| i * i).collect::<Vec<_>>());
+    fuzz(4, &(0..100).map(|i| i as f64 / 3.0).collect::<Vec<_>>());
+    fuzz(5, &(0..100).map(|i| i as f32 * 10.0).collect::<Vec<_>>());
+    fuzz(6, &(0..100).map(|i| i % 7 == 0).collect::<Vec<_>>());
+    fuzz(12, &(0..50).map(|i| (i as u128) << (i * 2)).collect::<Vec<_>>());
+    fuzz(13, &(0..50).map(|i| -(i as i128) << (i * 2)).collect::<Vec<_>>());
+}
+
+#[test]
+fn fuzz_strings() {
+    fuzz(7, &(0..50).map(|i| format!("{}", i % 5)).collect::<Vec<_>>());
+    fuzz(8, &"a string".to_owned());
+}
+
+#[test]
+fn fuzz_nested() {
+    fuzz(9, &vec![vec![vec![1u32, 2], vec![]], vec![vec![3]]]);
+    fuzz(10, &vec![Some(1u8), None, Some(3)]);
+}
+
+#[test]
+fn fuzz_random_bytes() {
+    let mut rng = StdRng::seed_from_u64(11);
+    for _ in 0..5000 {
+        let len = rng.gen_range(0, 64);
+        let input: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            read_all::<Record>(&input);
+            read_all::<Vec<Record>>(&input);
+        }));
+        if result.is_err() {
+            panic!("Panicked while reading {:?}", input);
+        }
+    }
+}
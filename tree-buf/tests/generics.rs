@@ -0,0 +1,95 @@
+use std::fmt::Debug;
+use tree_buf::prelude::*;
+mod common;
+use common::*;
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Page<T> {
+    items: Vec<T>,
+    next: Option<u64>,
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Wrapper<T>(T);
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+    Empty,
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Keyed<K, V>
+where
+    K: Clone,
+{
+    key: K,
+    value: V,
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Labeled<'a, T> {
+    label: &'a str,
+    value: T,
+}
+
+// Does not implement Default, which the writer for Page<T> must not require
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Point {
+    x: u32,
+    y: u32,
+}
+
+#[test]
+fn generic_struct() {
+    round_trip(
+        &Page {
+            items: vec![1u32, 2, 3],
+            next: Some(4),
+        },
+        21,
+        30,
+    );
+    round_trip(
+        &Page {
+            items: vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }],
+            next: None,
+        },
+        28,
+        34,
+    );
+}
+
+#[test]
+fn generic_tuple_struct() {
+    round_trip(&Wrapper(String::from("wrapped")), 10, 17);
+    round_trip(&Wrapper(Wrapper(5u8)), 4, 8);
+}
+
+#[test]
+fn generic_enum() {
+    round_trip(&OneOrMany::One(1.5f64), 14, 23);
+    round_trip(&OneOrMany::Many(vec![1u64, 2]), 12, 20);
+    round_trip(&OneOrMany::<Point>::Empty, 8, 14);
+}
+
+#[test]
+fn where_clause() {
+    round_trip(
+        &Keyed {
+            key: 1u32,
+            value: String::from("one"),
+        },
+        16,
+        26,
+    );
+}
+
+#[test]
+fn lifetime_and_type_parameter() {
+    let value = Labeled { label: "label", value: 10u32 };
+    let bytes = write(&value);
+    let result: Labeled<u32> = read(&bytes).unwrap();
+    assert_eq!(value, result);
+}
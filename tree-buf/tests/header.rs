@@ -0,0 +1,66 @@
+use tree_buf::prelude::*;
+use tree_buf::{decode_options, encode_options, options, ReadError, FORMAT_VERSION, MIN_FORMAT_VERSION};
+
+#[derive(Read, Write, PartialEq, Debug)]
+pub struct Data {
+    id: u32,
+    name: String,
+}
+
+fn data() -> Data {
+    Data { id: 1, name: "name".to_owned() }
+}
+
+#[test]
+fn header_round_trip() {
+    let options = encode_options! { options::Header };
+    let bytes = tree_buf::write_with_options(&data(), &options);
+    let plain = tree_buf::write(&data());
+    assert_eq!(bytes.len(), plain.len() + 5);
+    assert_eq!(&bytes[..4], &[0xFF, b'T', b'B', b'F']);
+    assert_eq!(bytes[4], FORMAT_VERSION);
+    assert_eq!(data(), tree_buf::read(&bytes).unwrap());
+}
+
+#[test]
+fn require_header() {
+    let options = decode_options! { options::RequireHeader };
+    let plain = tree_buf::write(&data());
+    assert_eq!(tree_buf::read_with_options::<Data>(&plain, &options), Err(ReadError::InvalidFormat));
+
+    let bytes = tree_buf::write_with_options(&data(), &encode_options! { options::Header });
+    assert_eq!(tree_buf::read_with_options(&bytes, &options), Ok(data()));
+}
+
+#[test]
+fn unsupported_version() {
+    let mut bytes = vec![0xFF, b'T', b'B', b'F', 99];
+    bytes.extend(tree_buf::write(&data()));
+    assert_eq!(tree_buf::read::<Data>(&bytes), Err(ReadError::UnsupportedFormatVersion(99)));
+}
+
+#[test]
+fn not_tree_buf() {
+    assert_eq!(tree_buf::read::<Data>(b"\xFFnot tree-buf").unwrap_err().kind(), &ReadError::InvalidFormat);
+}
+
+#[test]
+fn target_format_version() {
+    let options = encode_options! { options::Header, options::TargetFormatVersion(1) };
+    let bytes = tree_buf::write_with_options(&data(), &options);
+    assert_eq!(bytes[4], 1);
+    assert_eq!(data(), tree_buf::read(&bytes).unwrap());
+}
+
+#[test]
+fn target_unknown_format_version() {
+    let options = encode_options! { options::Header, options::TargetFormatVersion(FORMAT_VERSION + 1) };
+    let bytes = tree_buf::write_with_options(&data(), &options);
+    assert_eq!(bytes[4], FORMAT_VERSION);
+    assert_eq!(data(), tree_buf::read(&bytes).unwrap());
+
+    let options = encode_options! { options::Header, options::TargetFormatVersion(0) };
+    let bytes = tree_buf::write_with_options(&data(), &options);
+    assert_eq!(bytes[4], MIN_FORMAT_VERSION);
+    assert_eq!(data(), tree_buf::read(&bytes).unwrap());
+}
@@ -0,0 +1,77 @@
+use tree_buf::internal::encodings::varint::encode_prefix_varint;
+use tree_buf::internal::{ArrayTypeId, RootTypeId};
+use tree_buf::prelude::*;
+use tree_buf::{decode_options, options, read_with_options, Limit, ReadError, Value};
+
+fn limit_exceeded<T: std::fmt::Debug>(result: Result<T, ReadError>) -> Limit {
+    match result.unwrap_err().kind() {
+        ReadError::LimitExceeded(limit) => *limit,
+        other => panic!("Expected a limit to be exceeded, but got {:?}", other),
+    }
+}
+
+#[test]
+fn max_depth() {
+    let bytes = write(&vec![vec![1u32, 2], vec![3]]);
+    assert!(read::<Vec<Vec<u32>>>(&bytes).is_ok());
+    let options = decode_options! { options::MaxDepth(2) };
+    assert_eq!(limit_exceeded(read_with_options::<Vec<Vec<u32>>>(&bytes, &options)), Limit::Depth);
+}
+
+#[test]
+fn deeply_nested_document() {
+    // Would overflow the stack without a limit
+    let mut bytes = vec![u8::from(RootTypeId::Array1); 1_000_000];
+    bytes.push(u8::from(RootTypeId::Void));
+    assert_eq!(limit_exceeded(read::<Value>(&bytes)), Limit::Depth);
+}
+
+#[test]
+fn max_collection_length() {
+    let options = decode_options! { options::MaxCollectionLength(3) };
+    let bytes = write(&vec![1u32, 2, 3]);
+    assert_eq!(read_with_options(&bytes, &options), Ok(vec![1u32, 2, 3]));
+    let bytes = write(&vec![1u32, 2, 3, 4]);
+    assert_eq!(limit_exceeded(read_with_options::<Vec<u32>>(&bytes, &options)), Limit::CollectionLength);
+    let bytes = write(&vec![vec![1u32], vec![1, 2, 3, 4]]);
+    assert_eq!(limit_exceeded(read_with_options::<Vec<Vec<u32>>>(&bytes, &options)), Limit::CollectionLength);
+    assert_eq!(limit_exceeded(read_with_options::<Value>(&bytes, &options)), Limit::CollectionLength);
+}
+
+#[test]
+fn max_string_length() {
+    let options = decode_options! { options::MaxStringLength(5) };
+    let bytes = write(&"short".to_owned());
+    assert_eq!(read_with_options(&bytes, &options), Ok("short".to_owned()));
+    let bytes = write(&"longer".to_owned());
+    assert_eq!(limit_exceeded(read_with_options::<String>(&bytes, &options)), Limit::StringLength);
+    let bytes = write(&vec!["short".to_owned(), "longer".to_owned()]);
+    assert_eq!(limit_exceeded(read_with_options::<Vec<String>>(&bytes, &options)), Limit::StringLength);
+}
+
+#[test]
+fn max_allocation() {
+    let options = decode_options! { options::MaxAllocation(32) };
+    let bytes = write(&vec![1u64; 4]);
+    assert_eq!(read_with_options(&bytes, &options), Ok(vec![1u64; 4]));
+    let bytes = write(&vec![1u64; 5]);
+    assert_eq!(limit_exceeded(read_with_options::<Vec<u64>>(&bytes, &options)), Limit::Allocation);
+}
+
+#[test]
+fn hostile_length() {
+    let mut bytes = vec![u8::from(RootTypeId::ArrayN)];
+    encode_prefix_varint(1 << 28, &mut bytes);
+    bytes.push(u8::from(ArrayTypeId::Void));
+    assert_eq!(limit_exceeded(read::<Vec<u64>>(&bytes)), Limit::Allocation);
+}
+
+#[test]
+fn default_limits() {
+    let mut bytes = vec![u8::from(RootTypeId::ArrayN)];
+    encode_prefix_varint(2, &mut bytes);
+    bytes.push(u8::from(ArrayTypeId::ArrayFixed));
+    encode_prefix_varint(1 << 40, &mut bytes);
+    bytes.push(u8::from(ArrayTypeId::Void));
+    assert_eq!(limit_exceeded(read::<Vec<Vec<u64>>>(&bytes)), Limit::CollectionLength);
+}
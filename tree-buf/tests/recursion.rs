@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use tree_buf::prelude::*;
+mod common;
+use common::*;
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Node {
+    value: u32,
+    children: Vec<Node>,
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct List {
+    value: u32,
+    next: Option<Box<List>>,
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub enum Expr {
+    Literal(i64),
+    Negate(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Call { name: String, args: Vec<Expr> },
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(HashMap<String, Json>),
+}
+
+fn leaf(value: u32) -> Node {
+    Node { value, children: vec![] }
+}
+
+#[test]
+fn tree() {
+    round_trip(&leaf(1), 18, 42);
+    let tree = Node {
+        value: 1,
+        children: vec![
+            Node {
+                value: 2,
+                children: vec![leaf(4), leaf(5)],
+            },
+            leaf(3),
+        ],
+    };
+    round_trip(&tree, 85, 95);
+}
+
+#[test]
+fn linked_list() {
+    let list = List {
+        value: 1,
+        next: Some(Box::new(List {
+            value: 2,
+            next: Some(Box::new(List { value: 3, next: None })),
+        })),
+    };
+    round_trip(&list, 42, 58);
+}
+
+#[test]
+fn expression() {
+    let expr = Expr::Add(
+        Box::new(Expr::Literal(1)),
+        Box::new(Expr::Negate(Box::new(Expr::Call {
+            name: "max".to_owned(),
+            args: vec![Expr::Literal(2), Expr::Negate(Box::new(Expr::Literal(3)))],
+        }))),
+    );
+    round_trip(&expr, 86, 114);
+}
+
+#[test]
+fn json_like() {
+    let mut object = HashMap::new();
+    object.insert("flag".to_owned(), Json::Bool(true));
+    object.insert("items".to_owned(), Json::Array(vec![Json::Number(1.5), Json::Null, Json::String("s".to_owned())]));
+    let value = Json::Array(vec![Json::Object(object), Json::Array(vec![]), Json::Number(2.0)]);
+    round_trip(&value, 130, 149);
+}
+
+// Each level of recursion is one or two levels of branches,
+// so data this deep must be within the default MaxDepth.
+#[test]
+fn deep_with_default_options() {
+    let mut tree = leaf(0);
+    for value in 1..100 {
+        tree = Node {
+            value,
+            children: vec![tree, leaf(value)],
+        };
+    }
+    assert_eq!(read::<Node>(&write(&tree)), Ok(tree));
+
+    let mut list = List { value: 0, next: None };
+    for value in 1..200 {
+        list = List { value, next: Some(Box::new(list)) };
+    }
+    assert_eq!(read::<List>(&write(&list)), Ok(list));
+}
@@ -2,7 +2,10 @@ use std::fmt::Debug;
 use tree_buf::prelude::*;
 mod common;
 use common::*;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
 use tree_buf::encode_options;
 use tree_buf::options;
 
@@ -22,9 +25,28 @@ mod hide_namespace {
         pub one: Vec<u64>,
         pub tup: (f64, f64),
     }
+
+    #[derive(Read, Write, PartialEq, Debug, Clone)]
+    pub struct Meters(pub f64);
+
+    #[derive(Read, Write, PartialEq, Debug, Clone)]
+    pub struct Pair(pub u64, pub String);
+
+    #[derive(Read, Write, PartialEq, Debug, Clone)]
+    pub struct Marker;
+
+    #[derive(Read, Write, PartialEq, Debug, Clone)]
+    #[tree_buf(transparent)]
+    pub struct Id(pub u32);
+
+    #[derive(Read, Write, PartialEq, Debug, Clone)]
+    #[tree_buf(transparent)]
+    pub struct Name {
+        pub value: String,
+    }
 }
 
-use hide_namespace::{Bits, Bobs};
+use hide_namespace::{Bits, Bobs, Id, Marker, Meters, Name, Pair};
 
 // TODO: Compare to Avro - https://github.com/flavray/avro-rs
 
@@ -99,16 +121,124 @@ fn int_vec() {
     round_trip(&vec![99u64, 100], 6, 10);
 }
 
+#[test]
+fn signed_ints_root() {
+    round_trip(&0i64, 1, 5);
+    round_trip(&-1i64, 1, 6);
+    round_trip(&-2i64, 2, 7);
+    round_trip(&-128i64, 2, 7);
+    round_trip(&-129i64, 3, 9);
+    round_trip(&-40000i32, 4, 11);
+    round_trip(&-8388609i32, 5, 11);
+    round_trip(&i32::MIN, 5, 12);
+    round_trip(&-140737488355329i64, 8, 14);
+    round_trip(&i64::MIN, 9, 9);
+    round_trip(&i64::MAX, 9, 8);
+    round_trip(&i8::MIN, 2, 7);
+    round_trip(&i16::MIN, 3, 11);
+}
+
+#[test]
+fn signed_int_vec() {
+    round_trip(&vec![-1i32, 2, -3, 4, i32::MIN, i32::MAX], 17, 33);
+    round_trip(&vec![-1i8, 1, -100, 100], 9, 15);
+    // Non-negative values are written the same as unsigned
+    round_trip(&vec![99i64, 100], 6, 10);
+}
+
+#[test]
+fn platform_width_ints() {
+    round_trip(&75339usize, 4, 10);
+    round_trip(&-129isize, 3, 9);
+    round_trip(&vec![99usize, 100], 6, 10);
+    round_trip(&vec![-1isize, 1, -100, 100], 9, 15);
+
+    #[derive(Read, Write, Debug, PartialEq, Clone)]
+    struct Span {
+        start: usize,
+        len: usize,
+        offset: isize,
+    }
+    round_trip(&Span { start: 10, len: 300, offset: -5 }, 25, 35);
+}
+
+#[test]
+fn wide_ints() {
+    round_trip(&u128::MAX, 17, 15);
+    round_trip(&i128::MIN, 17, 16);
+    round_trip(&vec![u128::MAX, 0, 1 << 100, 7], 24, 44);
+    round_trip(&vec![i128::MIN, -1, 1, i128::MAX], 20, 22);
+    // Values which fit in 64 bits are written the same as u64
+    serialize_eq(&5u128, &5u64, 2);
+    serialize_eq(&vec![1u128, 2, 3], &vec![1u64, 2, 3], 7);
+    serialize_eq(&vec![-1i128, 2, -3], &vec![-1i64, 2, -3], 8);
+
+    // Version 1 of the format has no 128 bit integers,
+    // so the high and low 64 bits are written as a tuple instead.
+    let options = encode_options! { options::TargetFormatVersion(1) };
+    let bytes = tree_buf::write_with_options(&u128::MAX, &options);
+    assert_eq!(read::<(u64, u64)>(&bytes), Ok((u64::MAX, u64::MAX)));
+    assert_eq!(read::<u128>(&bytes), Ok(u128::MAX));
+    let bytes = tree_buf::write_with_options(&i128::MIN, &options);
+    assert_eq!(read::<(i64, u64)>(&bytes), Ok((i64::MIN, 0)));
+    assert_eq!(read::<i128>(&bytes), Ok(i128::MIN));
+    let data = vec![u128::MAX, 0, 1 << 100, 7];
+    let bytes = tree_buf::write_with_options(&data, &options);
+    assert_eq!(read::<Vec<(u64, u64)>>(&bytes), Ok(data.iter().map(|v| ((*v >> 64) as u64, *v as u64)).collect()));
+    assert_eq!(read::<Vec<u128>>(&bytes), Ok(data));
+    let data = vec![i128::MIN, -1, 1, i128::MAX];
+    assert_eq!(read::<Vec<i128>>(&tree_buf::write_with_options(&data, &options)), Ok(data));
+}
+
 #[test]
 fn float64_vec() {
     round_trip(&vec![0.99], 10, 16);
-    round_trip(&vec![0.01, 0.02, 0.03, 0.04], 36, 65);
+    round_trip(&vec![0.01, 0.02, 0.03, 0.04], 36, 45);
 }
 
 #[test]
 fn float32_vec() {
     round_trip(&vec![0.99f32], 6, 14);
-    round_trip(&vec![0.01f32, 0.02, 0.03, 0.04], 20, 38);
+    round_trip(&vec![0.01f32, 0.02, 0.03, 0.04], 20, 29);
+}
+
+// Eg: readings where 0.0 and -1.0 are sentinels for missing values
+#[test]
+fn float_vec_using_dictionary() {
+    let data = (0..1000u64).map(|i| [0.0, -1.0, 12.5, 99.9][(scatter(i) % 4) as usize]).collect::<Vec<f64>>();
+    round_trip(&data, 319, 605);
+
+    let options = encode_options! { options::TargetFormatVersion(7) };
+    let without_dictionary = tree_buf::write_with_options(&data, &options);
+    assert!(write(&data).len() * 10 < without_dictionary.len());
+    assert_eq!(read::<Vec<f64>>(&without_dictionary), Ok(data));
+}
+
+#[test]
+fn float_vec_using_rle() {
+    let data = (0..1000u64).map(|i| (i / 100) as f32 * 0.1).collect::<Vec<f32>>();
+    round_trip(&data, 58, 85);
+}
+
+// Sensor readings with noise in the low bits of the mantissa
+#[test]
+fn noisy_f64_vec_using_byte_stream_split() {
+    let data = (0..1000u64).map(|i| 20.0 + (i as f64 * 0.01).sin() + (scatter(i) >> 40) as f64 * 1e-9).collect::<Vec<f64>>();
+    round_trip(&data, 6131, 6165);
+
+    let options = encode_options! { options::TargetFormatVersion(8) };
+    let without_split = tree_buf::write_with_options(&data, &options);
+    assert!(write(&data).len() < without_split.len());
+    assert_eq!(read::<Vec<f64>>(&without_split), Ok(data));
+}
+
+// Runs and dictionaries must not merge 0.0 with -0.0, or split NaN
+#[test]
+fn float_vec_bitwise() {
+    let data = vec![0.0, -0.0, -0.0, f64::NAN, f64::NAN, f64::NAN, 0.0, -0.0, 0.0, 0.0];
+    let bytes = write(&data);
+    let read = read::<Vec<f64>>(&bytes).unwrap();
+    assert_eq!(data.iter().map(|f| f.to_bits()).collect::<Vec<_>>(), read.iter().map(|f| f.to_bits()).collect::<Vec<_>>());
 }
 
 #[test]
@@ -138,7 +268,7 @@ fn lossy_f64_vec() {
 
 #[test]
 fn nested_float_vec() {
-    round_trip(&vec![vec![10.0, 11.0], vec![], vec![99.0]], 24, 32);
+    round_trip(&vec![vec![10.0, 11.0], vec![], vec![99.0]], 23, 32);
 }
 
 #[test]
@@ -149,14 +279,14 @@ fn array_tuple() {
 #[test]
 fn item() {
     let item = make_item();
-    round_trip(&item, 144, 221);
+    round_trip(&item, 132, 179);
 }
 
 #[test]
 fn item_vec() {
     let item = make_item();
     let item = vec![item; 5];
-    round_trip(&item, 379, 646);
+    round_trip(&item, 213, 267);
 }
 
 #[test]
@@ -237,6 +367,14 @@ fn various_types() {
 
 #[test]
 fn conversions() {
+    serialize_eq(&5u32, &5i8, 2);
+    serialize_eq(&-5i8, &-5i64, 2);
+    serialize_eq(&vec![1u64, 2, 300], &vec![1i16, 2, 300], 8);
+    serialize_eq(&vec![-1i16, 2, -300], &vec![-1i64, 2, -300], 9);
+    serialize_eq(&vec![1u64, 2, 300], &vec![1usize, 2, 300], 8);
+    serialize_eq(&vec![1usize, 2, 300], &vec![1u32, 2, 300], 8);
+    serialize_eq(&-5isize, &-5i32, 2);
+
     // TODO: f32
     //serialize_eq(1.0f64, 1.0f32, 0);
     //serialize_eq(1.0f32, 1.0f64, 0);
@@ -387,6 +525,104 @@ fn maps_void() {
     round_trip(&data, 10, 13);
 }
 
+#[test]
+fn btree_maps() {
+    round_trip(&BTreeMap::<u32, u32>::new(), 2, 8);
+    let data = (0..5u32).map(|i| (format!("key{}", i), vec![i; i as usize])).collect::<BTreeMap<_, _>>();
+    round_trip(&data, 46, 69);
+}
+
+#[test]
+fn sets() {
+    let data = (0..5u32).map(|i| i * 3).collect::<BTreeSet<_>>();
+    round_trip(&data, 8, 14);
+    let data = ["a", "b", "c"].iter().map(|s| s.to_string()).collect::<HashSet<_>>();
+    round_trip(&data, None, None);
+}
+
+#[test]
+fn sequences() {
+    round_trip(&(0..5u64).collect::<VecDeque<_>>(), 8, 14);
+    round_trip(&(0..5u64).collect::<LinkedList<_>>(), 8, 14);
+    round_trip(&VecDeque::<String>::new(), 1, 6);
+
+    #[derive(Read, Write, Debug, PartialEq, Clone)]
+    struct Collections {
+        queue: VecDeque<u8>,
+        tags: HashSet<String>,
+        sorted: BTreeSet<i32>,
+        ordered: BTreeMap<String, f64>,
+    }
+    let data = Collections {
+        queue: vec![1, 2, 3].into(),
+        tags: vec!["tag".to_owned()].into_iter().collect(),
+        sorted: vec![-1, 4].into_iter().collect(),
+        ordered: vec![("one".to_owned(), 1.0)].into_iter().collect(),
+    };
+    round_trip(&data, 53, 84);
+}
+
+// Collections with the same encoding may be read as one another
+#[test]
+fn collections_interchangeable() {
+    let vec = vec![3u32, 1, 2];
+    let set = vec.iter().cloned().collect::<BTreeSet<_>>();
+    serialize_eq(&vec, &set, 7);
+    serialize_eq(&set, &vec![1u32, 2, 3], 7);
+    serialize_eq(&vec, &vec.iter().cloned().collect::<HashSet<_>>(), None);
+    serialize_eq(&vec, &vec.iter().cloned().collect::<VecDeque<_>>(), 7);
+    serialize_eq(&vec.iter().cloned().collect::<LinkedList<_>>(), &vec, 7);
+    serialize_eq(&vec![vec, vec![]], &vec![set, BTreeSet::new()], 12);
+
+    // Duplicates are removed when reading into a set
+    serialize_eq(&vec![1u32, 1, 2], &vec![1u32, 2].into_iter().collect::<HashSet<_>>(), None);
+
+    let btree = (0..3u32).map(|i| (i, i.to_string())).collect::<BTreeMap<_, _>>();
+    let hash = btree.clone().into_iter().collect::<HashMap<_, _>>();
+    serialize_eq(&btree, &hash, 15);
+    serialize_eq(&hash, &btree, None);
+    serialize_eq(&vec![btree.clone(), btree.clone()], &vec![hash.clone(), hash], 27);
+}
+
+#[test]
+fn smart_pointers() {
+    round_trip(&Rc::new(5u32), 2, 6);
+    round_trip(&vec![Rc::new("rc".to_owned())], 4, 12);
+    round_trip(&Arc::new(vec![Some(1.5f64), None]), 16, 20);
+    round_trip(&Box::<str>::from("boxed"), 7, 14);
+    round_trip(&Rc::<str>::from("shared"), 8, 15);
+    round_trip(&Arc::<str>::from(""), 1, 6);
+    round_trip(&Box::<[u8]>::from(&[1, 2, 3][..]), 7, 10);
+    round_trip(&Arc::<[String]>::from(vec!["a".to_owned(), "b".to_owned()]), 8, 14);
+    round_trip(&Cow::<[u16]>::Owned(vec![300, 200]), 8, 14);
+
+    #[derive(Read, Write, Debug, PartialEq, Clone)]
+    struct Config {
+        name: Arc<str>,
+        shared: Arc<Vec<u64>>,
+        hosts: Box<[Box<str>]>,
+    }
+    let data = Config {
+        name: "config".into(),
+        shared: Arc::new(vec![1, 2]),
+        hosts: vec!["a.example".into(), "b.example".into()].into(),
+    };
+    round_trip(&data, 57, 70);
+}
+
+// Wrappers are written exactly like the type they wrap
+#[test]
+fn smart_pointers_interchangeable() {
+    serialize_eq(&Arc::<str>::from("name"), &"name".to_owned(), 6);
+    serialize_eq(&"name".to_owned(), &Rc::<str>::from("name"), 6);
+    serialize_eq(&vec!["a".to_owned(), "b".to_owned()], &vec![Box::<str>::from("a"), "b".into()], 8);
+    serialize_eq(&Rc::new(1u8), &1u8, 1);
+    serialize_eq(&vec![1u8, 2], &vec![Arc::new(1u8), Arc::new(2)], 6);
+    serialize_eq(&Box::<[u32]>::from(&[1, 2][..]), &vec![1u32, 2], 6);
+    serialize_eq(&vec![1u32, 2], &Rc::<[u32]>::from(&[1, 2][..]), 6);
+    serialize_eq(&vec![1u32, 2], &Cow::<[u32]>::Borrowed(&[1, 2]), 6);
+}
+
 #[test]
 fn fixed_arrays() {
     round_trip(&[0u32, 1, 2, 3], 8, 10);
@@ -447,7 +683,72 @@ fn long_bool_runs() {
         }
         data.push(false);
     }
-    round_trip(&data, 36, 68);
+    round_trip(&data, 20, 34);
+}
+
+#[test]
+fn monotonic_ids_using_delta() {
+    let data = (1_000_000u64..1_001_000).collect::<Vec<_>>();
+    round_trip(&data, 18, 26);
+
+    let options = encode_options! { options::TargetFormatVersion(4) };
+    let without_delta = tree_buf::write_with_options(&data, &options);
+    assert!(write(&data).len() * 100 < without_delta.len());
+    assert_eq!(read::<Vec<u64>>(&without_delta), Ok(data));
+}
+
+#[test]
+fn timestamps_using_delta_of_delta() {
+    let data = (0..1000u64).map(|i| 1_600_000_000_000 + i * 1000).collect::<Vec<_>>();
+    round_trip(&data, 22, 32);
+}
+
+#[test]
+fn wrapping_deltas() {
+    let data = vec![u64::MAX, 0, u64::MAX, 1, u64::MAX - 1, 2];
+    round_trip(&data, 10, 16);
+
+    let data = (0..100u32).map(|i| if i % 2 == 0 { 0 } else { u32::MAX - i }).collect::<Vec<_>>();
+    round_trip(&data, 54, 100);
+}
+
+// Deterministic values in no particular order, so that delta encoding doesn't help
+fn scatter(i: u64) -> u64 {
+    let i = i.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    let i = (i ^ (i >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    i ^ (i >> 31)
+}
+
+// Eg: sensor ids, which are in a narrow range far from zero but in no particular order
+#[test]
+fn clustered_ints_using_frame_of_reference() {
+    let data = (0..1000u64).map(|i| 3_000_000_000 + scatter(i) % 1000).collect::<Vec<_>>();
+    round_trip(&data, 1306, 2606);
+
+    let options = encode_options! { options::TargetFormatVersion(5) };
+    let without_frame_of_reference = tree_buf::write_with_options(&data, &options);
+    assert!(write(&data).len() < without_frame_of_reference.len());
+    assert_eq!(read::<Vec<u64>>(&without_frame_of_reference), Ok(data));
+}
+
+#[test]
+fn outliers_using_patched_frame_of_reference() {
+    let data = (0..1000u64).map(|i| if i % 100 == 0 { 1 << 40 } else { scatter(i) % 64 }).collect::<Vec<_>>();
+    round_trip(&data, 842, 1678);
+
+    let options = encode_options! { options::TargetFormatVersion(5) };
+    assert!(write(&data).len() < tree_buf::write_with_options(&data, &options).len());
+}
+
+// Bursts of small and wide values, with one value too wide to lower the array to u32
+#[test]
+fn wide_ints_using_simple_8b() {
+    let mut data = (0..1000u64).map(|i| if i / 32 % 2 == 0 { scatter(i) % 4 } else { scatter(i) >> 34 }).collect::<Vec<_>>();
+    data[500] = 1 << 40;
+    round_trip(&data, 2224, 4442);
+
+    let options = encode_options! { options::TargetFormatVersion(6) };
+    assert!(write(&data).len() < tree_buf::write_with_options(&data, &options).len());
 }
 
 #[test]
@@ -481,7 +782,7 @@ fn broken_gorilla() {
     let broken = [-75.01536474599993, -75.00911189799993, 114.37647545700004];
 
     let mut bytes = Vec::new();
-    gorilla::compress((&broken[..]).iter().copied(), &mut bytes).unwrap();
+    gorilla::compress((&broken[..]).iter().copied(), &mut bytes);
     let out: Vec<f64> = gorilla::decompress(&bytes[..]).unwrap();
     assert_eq!(&broken[..], &out[..]);
 
@@ -505,7 +806,7 @@ fn broken_gorilla() {
         let values = &values[min..max];
         std::panic::catch_unwind(|| {
             let mut bytes = Vec::new();
-            gorilla::compress(values.iter().copied(), &mut bytes).unwrap();
+            gorilla::compress(values.iter().copied(), &mut bytes);
             let out: Vec<f64> = gorilla::decompress(&bytes[..]).unwrap();
             assert_eq!(values, &out[..]);
         })
@@ -529,3 +830,32 @@ fn broken_gorilla() {
     }
 }
 */
+
+#[test]
+fn tuple_structs() {
+    round_trip(&Meters(2.5), 10, 15);
+    round_trip(&Pair(7, "seven".to_owned()), 10, 19);
+    // Shares a schema with tuples
+    serialize_eq(&Pair(7, "seven".to_owned()), &(7u64, "seven".to_owned()), 10);
+    serialize_eq(&(2.5f64,), &Meters(2.5), 10);
+}
+
+#[test]
+fn one_tuple() {
+    round_trip(&(5u32,), 3, 7);
+}
+
+#[test]
+fn unit_struct() {
+    round_trip(&Marker, 1, 3);
+}
+
+#[test]
+fn transparent_structs() {
+    round_trip(&Id(5), 2, 6);
+    round_trip(&Name { value: "name".to_owned() }, 6, 14);
+    // Transparent structs are serialized the same as their only field
+    assert_eq!(write(&Id(500)), write(&500u32));
+    assert_eq!(write(&vec![Id(1), Id(2)]), write(&vec![1u32, 2]));
+    serialize_eq(&"name", &Name { value: "name".to_owned() }, 6);
+}
@@ -52,3 +52,122 @@ fn fixed_array_to_vec() {
     let vec: Vec<Vec<_>> = fixed.iter().map(|f| f.iter().copied().collect()).collect();
     serialize_eq(&fixed, &vec, 14);
 }
+
+#[test]
+fn rename_field() {
+    #[derive(Read, Write, PartialEq, Debug)]
+    pub struct Before {
+        old: u64,
+    }
+    #[derive(Read, Write, PartialEq, Debug)]
+    pub struct After {
+        #[tree_buf(rename = "old")]
+        new: u64,
+    }
+
+    serialize_eq(&Before { old: 1 }, &After { new: 1 }, 6);
+    serialize_eq(&After { new: 1 }, &Before { old: 1 }, 6);
+}
+
+#[test]
+fn alias_reads_old_name() {
+    #[derive(Write)]
+    pub struct Before {
+        user_name: String,
+    }
+    #[derive(Read, Write, PartialEq, Debug, Clone)]
+    pub struct After {
+        #[tree_buf(alias = "userName", alias = "name")]
+        login: String,
+    }
+
+    let before = Before { user_name: "a".to_owned() };
+    let after = After { login: "a".to_owned() };
+    serialize_eq(&before, &after, 12);
+    serialize_eq(&vec![before], &vec![after.clone()], 13);
+    // The new name is preferred when writing
+    round_trip(&after, 9, 15);
+}
+
+fn one_hundred() -> u64 {
+    100
+}
+
+#[test]
+fn default_for_missing_field() {
+    #[derive(Write)]
+    pub struct Before {
+        x: u64,
+    }
+    #[derive(Read, Write, PartialEq, Debug, Clone)]
+    pub struct After {
+        x: u64,
+        #[tree_buf(default = "one_hundred")]
+        y: u64,
+        #[tree_buf(default)]
+        z: Vec<u64>,
+    }
+
+    serialize_eq(&Before { x: 1 }, &After { x: 1, y: 100, z: vec![] }, 4);
+    serialize_eq(
+        &vec![Before { x: 1 }, Before { x: 2 }],
+        &vec![After { x: 1, y: 100, z: vec![] }, After { x: 2, y: 100, z: vec![] }],
+        9,
+    );
+    // When present, the field is read as usual
+    round_trip(&After { x: 1, y: 2, z: vec![3] }, 13, 22);
+}
+
+#[test]
+fn skip_field() {
+    #[derive(Read, Write, PartialEq, Debug, Clone)]
+    pub struct Cached {
+        value: u64,
+        #[tree_buf(skip)]
+        cache: Option<String>,
+        #[tree_buf(skip, default = "one_hundred")]
+        hundred: u64,
+    }
+    #[derive(Read, Write, PartialEq, Debug)]
+    pub struct Plain {
+        value: u64,
+    }
+
+    let cached = Cached {
+        value: 1,
+        cache: Some("cached".to_owned()),
+        hundred: 100,
+    };
+    let expected = Cached { cache: None, ..cached.clone() };
+    serialize_eq(&cached, &expected, 8);
+    serialize_eq(&vec![cached.clone(), cached], &vec![expected.clone(), expected], 12);
+    serialize_eq(
+        &Plain { value: 1 },
+        &Cached {
+            value: 1,
+            cache: None,
+            hundred: 100,
+        },
+        8,
+    );
+}
+
+#[test]
+fn rename_and_alias_enum_fields() {
+    #[derive(Write)]
+    pub enum Before {
+        Point { horizontal: u32, vertical: u32 },
+    }
+    #[derive(Read, Write, PartialEq, Debug)]
+    pub enum After {
+        Point {
+            #[tree_buf(alias = "horizontal")]
+            x: u32,
+            #[tree_buf(rename = "vertical")]
+            y: u32,
+        },
+    }
+
+    serialize_eq(&Before::Point { horizontal: 1, vertical: 2 }, &After::Point { x: 1, y: 2 }, 31);
+    serialize_eq(&vec![Before::Point { horizontal: 1, vertical: 2 }], &vec![After::Point { x: 1, y: 2 }], 32);
+}
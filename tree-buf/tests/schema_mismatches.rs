@@ -3,11 +3,11 @@ use tree_buf::prelude::*;
 use tree_buf::ReadError;
 use tree_buf::{Readable, Writable};
 
-fn expect_schema_mismatch<TIn: Writable + Default, TOut: Debug + Readable>() {
+fn expect_schema_mismatch<TIn: Writable + Default, TOut: Debug + for<'a> Readable<'a>>() {
     let data = TIn::default();
     let bytes = write(&data);
     let result = read::<TOut>(&bytes);
-    match result.unwrap_err() {
+    match result.unwrap_err().kind() {
         ReadError::SchemaMismatch => (),
         _ => assert!(false),
     }
@@ -36,3 +36,27 @@ fn missnamed_obj_field() {
 fn wrong_tuple_length() {
     expect_schema_mismatch::<(u64, u64), (u64, u64, u64)>();
 }
+
+#[test]
+fn negative_to_unsigned() {
+    let bytes = write(&-1i32);
+    assert_eq!(read::<u32>(&bytes).unwrap_err().kind(), &ReadError::SchemaMismatch);
+    let bytes = write(&vec![-1i32, 1]);
+    assert_eq!(read::<Vec<u32>>(&bytes).unwrap_err().kind(), &ReadError::SchemaMismatch);
+    let bytes = write(&vec![-1isize, 1]);
+    assert_eq!(read::<Vec<usize>>(&bytes).unwrap_err().kind(), &ReadError::SchemaMismatch);
+    let bytes = write(&-1isize);
+    assert_eq!(read::<usize>(&bytes).unwrap_err().kind(), &ReadError::SchemaMismatch);
+    let bytes = write(&vec![u128::MAX, 1]);
+    assert_eq!(read::<Vec<u64>>(&bytes).unwrap_err().kind(), &ReadError::SchemaMismatch);
+    let bytes = write(&u128::MAX);
+    assert_eq!(read::<i128>(&bytes).unwrap_err().kind(), &ReadError::SchemaMismatch);
+}
+
+#[test]
+fn signed_out_of_range() {
+    let bytes = write(&300i64);
+    assert_eq!(read::<i8>(&bytes).unwrap_err().kind(), &ReadError::SchemaMismatch);
+    let bytes = write(&vec![-300i64, 1]);
+    assert_eq!(read::<Vec<i8>>(&bytes).unwrap_err().kind(), &ReadError::SchemaMismatch);
+}
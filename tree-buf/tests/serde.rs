@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use tree_buf::Value;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Sample {
+    id: u64,
+    offset: i32,
+    ratio: f32,
+    name: String,
+    tags: Vec<String>,
+    parent: Option<Box<Sample>>,
+    kind: Kind,
+    unit: (),
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum Kind {
+    Empty,
+    Newtype(u8),
+    Tuple(u32, bool),
+    Struct { x: i64, y: char },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Meters(f64);
+
+fn round_trip<T: Serialize + for<'de> Deserialize<'de> + Debug + PartialEq>(value: &T, size: usize) {
+    let bytes = tree_buf::serde::write(value).unwrap();
+    let result: T = tree_buf::serde::read(&bytes).unwrap();
+    assert_eq!(value, &result);
+    assert_eq!(bytes.len(), size);
+}
+
+fn sample(id: u64) -> Sample {
+    Sample {
+        id,
+        offset: -(id as i32),
+        ratio: 0.5,
+        name: format!("sample {}", id),
+        tags: vec!["a".to_owned(); id as usize],
+        parent: None,
+        kind: Kind::Empty,
+        unit: (),
+    }
+}
+
+#[test]
+fn primitives() {
+    round_trip(&true, 1);
+    round_trip(&7u8, 2);
+    round_trip(&-7i64, 2);
+    round_trip(&1.5f64, 9);
+    round_trip(&'x', 2);
+    round_trip(&String::from("string"), 8);
+    round_trip(&Meters(2.5), 9);
+    round_trip(&Some(1u32), 1);
+    round_trip(&None::<u32>, 1);
+}
+
+// These would otherwise be read back as None
+#[test]
+fn some_void() {
+    assert!(tree_buf::serde::write(&Some(())).is_err());
+    assert!(tree_buf::serde::write(&Some(None::<u32>)).is_err());
+    assert!(tree_buf::serde::write(&vec![Some(()), None]).is_err());
+    round_trip(&Some(Some(1u32)), 1);
+}
+
+#[test]
+fn enums() {
+    round_trip(&Kind::Empty, 8);
+    round_trip(&Kind::Newtype(1), 10);
+    round_trip(&Kind::Tuple(2, true), 11);
+    round_trip(&Kind::Struct { x: -3, y: 'y' }, 17);
+    round_trip(&vec![Kind::Empty, Kind::Newtype(4), Kind::Tuple(5, false), Kind::Newtype(6)], 42);
+}
+
+#[test]
+fn structs() {
+    let mut value = sample(2);
+    value.parent = Some(Box::new(sample(1)));
+    value.kind = Kind::Struct { x: 1, y: 'z' };
+    round_trip(&value, 170);
+    round_trip(&(0..10).map(sample).collect::<Vec<_>>(), 206);
+}
+
+#[test]
+fn maps() {
+    let mut map = HashMap::new();
+    map.insert(1u32, vec![1.0f64, 2.0]);
+    round_trip(&map, 19);
+
+    let mut tree = BTreeMap::new();
+    tree.insert(String::from("one"), 1i8);
+    tree.insert(String::from("two"), -2i8);
+    round_trip(&tree, 17);
+}
+
+#[test]
+fn read_as_value() {
+    let bytes = tree_buf::serde::write(&Kind::Tuple(1, false)).unwrap();
+    let value: Value = tree_buf::read(&bytes).unwrap();
+    let expected = Value::Enum {
+        discriminant: "Tuple".to_owned(),
+        value: Box::new(Value::Array(vec![Value::Integer(1), Value::Bool(false)])),
+    };
+    assert_eq!(expected, value);
+}
+
+#[test]
+fn wide_integers() {
+    round_trip(&u128::MAX, 17);
+    round_trip(&u128::from(u64::MAX), 9);
+    round_trip(&i128::from(i64::MIN), 9);
+    round_trip(&i128::MIN, 17);
+    round_trip(&(i128::MAX as u128), 17);
+}
+
+#[test]
+fn schema_mismatch() {
+    let bytes = tree_buf::serde::write(&String::from("not a number")).unwrap();
+    assert!(tree_buf::serde::read::<u32>(&bytes).is_err());
+}
+
+#[test]
+fn blobs() {
+    let blob = tree_buf::Blob(vec![1, 2, 3]);
+    let bytes = tree_buf::write(&blob);
+    assert_eq!(tree_buf::serde::read::<Vec<u8>>(&bytes).unwrap(), vec![1, 2, 3]);
+    let bytes = tree_buf::serde::write(&Bytes(&[1, 2, 3])).unwrap();
+    assert_eq!(tree_buf::read::<Value>(&bytes), Ok(Value::Blob(vec![1, 2, 3])));
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
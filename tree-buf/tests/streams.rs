@@ -0,0 +1,53 @@
+use std::io::{Cursor, ErrorKind};
+use tree_buf::prelude::*;
+use tree_buf::{read_from, read_from_file, write_to, write_to_file};
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Item {
+    id: u64,
+    name: String,
+    values: Vec<f64>,
+}
+
+fn make_items() -> Vec<Item> {
+    (0..100)
+        .map(|i| Item {
+            id: i,
+            name: format!("item {}", i % 7),
+            values: vec![i as f64, 0.5],
+        })
+        .collect()
+}
+
+#[test]
+fn write_to_matches_write() {
+    let items = make_items();
+    let mut bytes = Vec::new();
+    write_to(&items, &mut bytes).unwrap();
+    assert_eq!(bytes, write(&items));
+}
+
+#[test]
+fn stream_round_trip() {
+    let items = make_items();
+    let mut bytes = Vec::new();
+    write_to(&items, &mut bytes).unwrap();
+    let parsed: Vec<Item> = read_from(Cursor::new(bytes)).unwrap();
+    assert_eq!(items, parsed);
+}
+
+#[test]
+fn read_from_invalid_data() {
+    let result = read_from::<Vec<Item>>(Cursor::new(vec![255u8, 0, 1]));
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn file_round_trip() {
+    let items = make_items();
+    let path = std::env::temp_dir().join(format!("tree-buf-file-round-trip-{}.tb", std::process::id()));
+    write_to_file(&items, &path).unwrap();
+    let parsed: Vec<Item> = read_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(items, parsed);
+}
@@ -0,0 +1,202 @@
+use std::collections::{BTreeMap, HashMap};
+use tree_buf::prelude::*;
+use tree_buf::Value;
+mod common;
+use common::*;
+
+fn object(fields: Vec<(&str, Value)>) -> Value {
+    Value::Object(fields.into_iter().map(|(k, v)| (k.to_owned(), v)).collect())
+}
+
+fn string(value: &str) -> Value {
+    Value::String(value.to_owned())
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub struct Item {
+    name: String,
+    count: u32,
+    tags: Vec<String>,
+    parent: Option<u64>,
+}
+
+#[derive(Read, Write, PartialEq, Debug, Clone)]
+pub enum Shape {
+    Circle(f64),
+    Square { side: u32 },
+    Empty,
+}
+
+#[test]
+fn scalars() {
+    round_trip(&Value::Null, 1, 3);
+    round_trip(&Value::Bool(true), 1, 5);
+    round_trip(&Value::Integer(300), 3, 8);
+    round_trip(&Value::Integer(-300), 3, 9);
+    round_trip(&Value::Integer(u64::MAX.into()), 9, 8);
+    round_trip(&Value::Integer(i64::MIN.into()), 9, 9);
+    round_trip(&Value::Integer(i128::MAX), 17, 15);
+    round_trip(&Value::Integer(i128::MIN), 17, 16);
+    round_trip(&Value::UInteger(u128::MAX), 17, 15);
+    round_trip(&Value::Float(1.0), 9, 14);
+    round_trip(&string("value"), 7, 14);
+}
+
+#[test]
+fn nested() {
+    let value = object(vec![
+        ("id", Value::Integer(1)),
+        ("name", string("first")),
+        (
+            "children",
+            Value::Array(vec![
+                object(vec![("id", Value::Integer(2)), ("weight", Value::Float(0.5))]),
+                object(vec![("id", Value::Integer(3)), ("weight", Value::Float(1.5))]),
+            ]),
+        ),
+        ("lookup", Value::Map(vec![(Value::Integer(1), string("one")), (Value::Integer(2), string("two"))])),
+        (
+            "choice",
+            Value::Enum {
+                discriminant: "Some".to_owned(),
+                value: Box::new(Value::Bool(false)),
+            },
+        ),
+    ]);
+    round_trip(&value, 93, 121);
+}
+
+#[test]
+fn read_typed_data() {
+    let item = Item {
+        name: "root".to_owned(),
+        count: 2,
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        parent: None,
+    };
+    let expected = object(vec![
+        ("name", string("root")),
+        ("count", Value::Integer(2)),
+        ("tags", Value::Array(vec![string("a"), string("b")])),
+        ("parent", Value::Null),
+    ]);
+    serialize_eq(&item, &expected, None);
+
+    let items = vec![
+        item.clone(),
+        Item {
+            name: "child".to_owned(),
+            count: 0,
+            tags: vec![],
+            parent: Some(5),
+        },
+    ];
+    let expected = Value::Array(vec![
+        expected,
+        object(vec![
+            ("name", string("child")),
+            ("count", Value::Integer(0)),
+            ("tags", Value::Array(vec![])),
+            ("parent", Value::Integer(5)),
+        ]),
+    ]);
+    serialize_eq(&items, &expected, None);
+}
+
+#[test]
+fn read_typed_enums_and_maps() {
+    let shapes = vec![Shape::Circle(1.5), Shape::Square { side: 2 }, Shape::Empty];
+    let expected = Value::Array(vec![
+        Value::Enum {
+            discriminant: "circle".to_owned(),
+            value: Box::new(Value::Float(1.5)),
+        },
+        Value::Enum {
+            discriminant: "square".to_owned(),
+            value: Box::new(object(vec![("side", Value::Integer(2))])),
+        },
+        Value::Enum {
+            discriminant: "empty".to_owned(),
+            value: Box::new(Value::Null),
+        },
+    ]);
+    serialize_eq(&shapes, &expected, None);
+
+    let mut map = HashMap::new();
+    map.insert(1u32, -1i32);
+    serialize_eq(&map, &Value::Map(vec![(Value::Integer(1), Value::Integer(-1))]), None);
+
+    serialize_eq(&(1u32, "two"), &Value::Array(vec![Value::Integer(1), string("two")]), None);
+}
+
+#[test]
+fn read_compressed_strings() {
+    // Repetitive strings are written with RLE and dictionary compression
+    let rle = vec!["same"; 20];
+    serialize_eq(&rle, &Value::Array(vec![string("same"); 20]), None);
+
+    let dictionary = (0..20).map(|i| if i % 3 == 0 { "fizz" } else { "buzz" }).collect::<Vec<_>>();
+    let expected = Value::Array(dictionary.iter().map(|s| string(s)).collect());
+    serialize_eq(&dictionary, &expected, None);
+}
+
+#[test]
+fn nullable_array() {
+    round_trip(&Value::Array(vec![Value::Integer(1), Value::Null, Value::Integer(2)]), 10, 14);
+}
+
+#[test]
+fn tuples() {
+    // Each index has a consistent kind, so these are written as tuples
+    let tuple = Value::Array(vec![Value::Integer(1), string("two"), Value::Null]);
+    round_trip(&tuple, 7, 18);
+}
+
+#[test]
+fn integers_beyond_i128() {
+    assert_eq!(read::<Value>(&write(&u128::MAX)), Ok(Value::UInteger(u128::MAX)));
+    assert_eq!(read::<Value>(&write(&(i128::MAX as u128))), Ok(Value::Integer(i128::MAX)));
+    let value = Value::Array(vec![Value::Integer(1), Value::UInteger(u128::MAX), Value::Integer(2)]);
+    round_trip(&value, 17, 21);
+    assert_eq!(read::<Vec<u128>>(&write(&value)), Ok(vec![1, u128::MAX, 2]));
+
+    // Negative integers can't be in the same array, so are written as an enum
+    let value = Value::Array(vec![Value::Integer(-1), Value::UInteger(u128::MAX)]);
+    let variant = |discriminant: &str, value| Value::Enum {
+        discriminant: discriminant.to_owned(),
+        value: Box::new(value),
+    };
+    let expected = Value::Array(vec![variant("integer", Value::Integer(-1)), variant("uinteger", Value::UInteger(u128::MAX))]);
+    assert_eq!(read::<Value>(&write(&value)), Ok(expected));
+}
+
+#[test]
+fn mixed_kinds_read_as_enum() {
+    let value = Value::Array(vec![Value::Array(vec![Value::Integer(1), string("two")]), Value::Array(vec![Value::Integer(3)])]);
+    let bytes = write(&value);
+    let result: Value = read(&bytes).unwrap();
+    let variant = |discriminant: &str, value| Value::Enum {
+        discriminant: discriminant.to_owned(),
+        value: Box::new(value),
+    };
+    let expected = Value::Array(vec![
+        Value::Array(vec![variant("integer", Value::Integer(1)), variant("string", string("two"))]),
+        Value::Array(vec![variant("integer", Value::Integer(3))]),
+    ]);
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn missing_object_fields_read_as_null() {
+    let mut first = BTreeMap::new();
+    first.insert("a".to_owned(), Value::Integer(1));
+    let mut second = BTreeMap::new();
+    second.insert("b".to_owned(), Value::Bool(true));
+    let value = Value::Array(vec![Value::Object(first), Value::Object(second)]);
+
+    let expected = Value::Array(vec![
+        object(vec![("a", Value::Integer(1)), ("b", Value::Null)]),
+        object(vec![("a", Value::Null), ("b", Value::Bool(true))]),
+    ]);
+    serialize_eq(&value, &expected, None);
+}